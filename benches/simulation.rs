@@ -0,0 +1,109 @@
+//! Plays a year on a large level with the game's own season systems in a headless app,
+//! to catch performance regressions in the rules. Run with `cargo bench --bench simulation`.
+
+use std::time::{Duration, Instant};
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use bevy_ecs_tilemap::{map::TilemapSize, tiles::TilePos};
+use bevy_jam_tree::{
+    game::{
+        season::{
+            state::{NextSeasonState, SeasonState},
+            Season, SeasonKind,
+        },
+        simulation::Board,
+        spawn::level::{CustomLevel, SelectedTile, LARGE_MAP_SIZE},
+        LevelSeed,
+    },
+    HeadlessPlugin,
+};
+use bevy_prng::WyRand;
+use rand_core::SeedableRng;
+
+const RUNS: u64 = 3;
+/// Time passing each frame, like a game running at 60 fps.
+const FRAME: Duration = Duration::from_nanos(16_666_667);
+/// A season still going after this many frames is stuck.
+const MAX_SEASON_FRAMES: u32 = 10_000;
+
+#[derive(Default)]
+struct Timings {
+    frames: u32,
+    total: Duration,
+    slowest: Duration,
+}
+
+impl Timings {
+    fn time(&mut self, app: &mut App) {
+        let start = Instant::now();
+        app.update();
+        let elapsed = start.elapsed();
+
+        self.frames += 1;
+        self.total += elapsed;
+        self.slowest = self.slowest.max(elapsed);
+    }
+
+    fn report(&self, name: &str) {
+        println!(
+            "{name}: {:?} on average, {:?} at most ({} frames)",
+            self.total / self.frames.max(1),
+            self.slowest,
+            self.frames
+        );
+    }
+}
+
+fn main() {
+    let size = TilemapSize {
+        x: LARGE_MAP_SIZE,
+        y: LARGE_MAP_SIZE,
+    };
+
+    // Spawning the level's tiles and trees
+    let mut spawn = Timings::default();
+    // The frame starting a season's simulation, running setups like `grow_logic`
+    let mut setup = Timings::default();
+    // Resolving tree actions and the transition to the next season, like `setup_transition`
+    let mut season = Timings::default();
+
+    for seed in 0..RUNS {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, HeadlessPlugin));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(FRAME));
+
+        // Trees on a quarter of the tiles keep the year busier than the starting forest
+        let mut rng = WyRand::seed_from_u64(seed);
+        app.insert_resource(CustomLevel(Board::generate(size, &mut rng)));
+        app.insert_resource(LevelSeed(seed));
+        spawn.time(&mut app);
+
+        for season_kind in SeasonKind::ALL {
+            app.world_mut()
+                .send_event(NextSeasonState(SeasonState::Simulation));
+            setup.time(&mut app);
+
+            // Plays until the next season waits for the player
+            let mut frames = 0;
+            while app.world().resource::<Season>().kind == season_kind {
+                // The cursor moves every frame, so `update_tile_color` runs every frame
+                app.world_mut().resource_mut::<SelectedTile>().0 = Some(TilePos {
+                    x: frames % size.x,
+                    y: seed as u32,
+                });
+                season.time(&mut app);
+
+                frames += 1;
+                assert!(
+                    frames < MAX_SEASON_FRAMES,
+                    "{season_kind:?} did not end with seed {seed}"
+                );
+            }
+        }
+    }
+
+    println!("A year on {}x{} ({} runs)", size.x, size.y, RUNS);
+    spawn.report("spawn level");
+    setup.report("season setup");
+    season.report("season frame");
+}
@@ -0,0 +1,114 @@
+//! Plays full games with each strategy of the computer player and reports their scores,
+//! to find the score ceiling of a level and to check balance changes for dominant strategies.
+//!
+//! Run with `cargo run --release --example strategies -- [level] [seeds]`.
+//! The level is `start` for the starting forest (the default), `generated` for a forest
+//! of random trees on a quarter of the tiles, different for each seed, or the name of a puzzle
+//! like `puzzle-sacrifice`, played with its own actions and years. Each strategy plays
+//! `seeds` games, 10 by default.
+
+use std::{process::ExitCode, time::Instant};
+
+use bevy_ecs_tilemap::map::TilemapSize;
+use bevy_jam_tree::game::{
+    ai::{play_game, Strategy},
+    puzzle::{Puzzle, PUZZLES},
+    season::SeasonActions,
+    simulation::Board,
+    spawn::level::MAP_SIZE,
+    YEARS,
+};
+use bevy_prng::WyRand;
+use rand_core::SeedableRng;
+
+const DEFAULT_SEEDS: u64 = 10;
+
+const SIZE: TilemapSize = TilemapSize {
+    x: MAP_SIZE,
+    y: MAP_SIZE,
+};
+
+enum Level {
+    Start,
+    Generated,
+    Puzzle(&'static Puzzle),
+}
+
+impl Level {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "start" => Some(Level::Start),
+            "generated" => Some(Level::Generated),
+            _ => PUZZLES
+                .iter()
+                .find(|puzzle| puzzle.name_key == name)
+                .map(Level::Puzzle),
+        }
+    }
+
+    fn board(&self, seed: u64) -> Board {
+        match self {
+            Level::Start => Board::level(SIZE),
+            Level::Generated => Board::generate(SIZE, &mut WyRand::seed_from_u64(seed)),
+            Level::Puzzle(puzzle) => puzzle.board(),
+        }
+    }
+
+    fn years(&self) -> u32 {
+        match self {
+            Level::Puzzle(puzzle) => puzzle.years,
+            Level::Start | Level::Generated => YEARS,
+        }
+    }
+
+    fn season_actions(&self) -> SeasonActions {
+        match self {
+            Level::Puzzle(puzzle) => puzzle.season_actions(),
+            Level::Start | Level::Generated => SeasonActions::default(),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let level_name = args.next().unwrap_or_else(|| "start".into());
+    let Some(level) = Level::parse(&level_name) else {
+        eprintln!("Unknown level {level_name:?}, expected `start`, `generated` or a puzzle name");
+        return ExitCode::FAILURE;
+    };
+    let seeds = match args.next().map(|seeds| seeds.parse::<u64>()) {
+        None => DEFAULT_SEEDS,
+        Some(Ok(seeds)) if seeds > 0 => seeds,
+        Some(_) => {
+            eprintln!("The number of seeds has to be a positive number");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!("{level_name} level, {seeds} seeds");
+    for strategy in Strategy::ALL {
+        let start = Instant::now();
+        let scores: Vec<usize> = (0..seeds)
+            .map(|seed| {
+                play_game(
+                    strategy,
+                    &level.board(seed),
+                    level.years(),
+                    level.season_actions(),
+                    seed,
+                )
+            })
+            .collect();
+
+        println!(
+            "{:?}: {:.1} points on average, {} at best, {} at worst ({:?})",
+            strategy,
+            scores.iter().sum::<usize>() as f32 / seeds as f32,
+            scores.iter().max().unwrap_or(&0),
+            scores.iter().min().unwrap_or(&0),
+            start.elapsed()
+        );
+    }
+
+    ExitCode::SUCCESS
+}
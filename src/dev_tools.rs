@@ -1,12 +1,20 @@
 //! Development tools for the game. This plugin is only enabled in dev builds.
 
-use bevy::{dev_tools::states::log_transitions, log::LogPlugin, prelude::*};
+use bevy::{
+    dev_tools::states::log_transitions, input::common_conditions::input_just_pressed,
+    log::LogPlugin, prelude::*,
+};
+use bevy_ecs_tilemap::tiles::TilePos;
 use bevy_inspector_egui::quick::WorldInspectorPlugin;
 
 use crate::{
     game::{
-        season::state::NextSeasonState,
-        spawn::tree::{DespawnTree, SpawnTree},
+        season::{
+            logic::{TreeAction, TreeActionSet},
+            state::{NextSeasonState, SeasonState},
+            Season,
+        },
+        spawn::tree::SpawnTree,
     },
     screen::Screen,
 };
@@ -21,12 +29,26 @@ pub(super) fn plugin(app: &mut App) {
     // Print state transitions in dev builds
     app.add_systems(Update, log_transitions::<Screen>);
 
+    app.add_systems(
+        Update,
+        (log_events::<NextSeasonState>, log_events::<SpawnTree>),
+    );
+
+    // Step through the simulation one tree action at a time
+    app.init_resource::<StepSimulation>();
+    app.configure_sets(
+        Update,
+        TreeActionSet.run_if(|step: Res<StepSimulation>| !step.0),
+    );
     app.add_systems(
         Update,
         (
-            log_events::<NextSeasonState>,
-            log_events::<SpawnTree>,
-            log_events::<DespawnTree>,
+            toggle_step_simulation.run_if(input_just_pressed(TOGGLE_STEP_KEY)),
+            step_tree_action.run_if(
+                in_state(Screen::Playing)
+                    .and_then(|step: Res<StepSimulation>| step.0)
+                    .and_then(input_just_pressed(STEP_KEY)),
+            ),
         ),
     );
 }
@@ -36,3 +58,52 @@ fn log_events<T: Event + std::fmt::Debug>(mut event_reader: EventReader<T>) {
         debug!("{:?}", event);
     }
 }
+
+const TOGGLE_STEP_KEY: KeyCode = KeyCode::F9;
+const STEP_KEY: KeyCode = KeyCode::F10;
+
+/// While enabled, tree actions only resolve one at a time on [`STEP_KEY`].
+#[derive(Debug, Default, Resource)]
+struct StepSimulation(bool);
+
+fn toggle_step_simulation(mut step: ResMut<StepSimulation>) {
+    step.0 = !step.0;
+    info!(
+        "Step simulation {}",
+        if step.0 { "enabled" } else { "disabled" }
+    );
+}
+
+/// Advances all tree actions to the next one that is due and resolves it,
+/// along with any others due at the same time.
+fn step_tree_action(
+    mut commands: Commands,
+    season: Res<Season>,
+    mut tree_action_q: Query<(Entity, &mut TreeAction, &TilePos)>,
+) {
+    if !matches!(season.state, SeasonState::Simulation) {
+        return;
+    }
+
+    // Finished actions that are not repeating have no time left and are removed this frame
+    let Some(step) = tree_action_q
+        .iter()
+        .map(|(_, tree_action, _)| tree_action.remaining())
+        .filter(|remaining| !remaining.is_zero())
+        .min()
+    else {
+        return;
+    };
+
+    for (entity, mut tree_action, tile_pos) in &mut tree_action_q {
+        if tree_action.tick(step) {
+            info!(
+                "{:?} at {:?} caused by {:?}",
+                tree_action.kind(),
+                tile_pos,
+                tree_action.cause()
+            );
+            tree_action.resolve(&mut commands, entity);
+        }
+    }
+}
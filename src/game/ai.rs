@@ -0,0 +1,412 @@
+//! A computer player. It plans the user actions of each season on a [`Board`] with one of
+//! several strategies, and can play the game by itself as a demo.
+
+use bevy::{
+    prelude::*,
+    tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task},
+    ui::Val::*,
+};
+use bevy_ecs_tilemap::tiles::TilePos;
+use bevy_prng::WyRand;
+use rand_core::{RngCore, SeedableRng};
+
+use crate::{
+    locale::LocalizedText,
+    screen::{Pause, Screen},
+    ui::{
+        palette::{BUTTON_TEXT, NODE_BACKGROUND},
+        prelude::*,
+    },
+};
+
+use super::{
+    season::{
+        state::{NextSeasonState, SeasonState},
+        Season, SeasonActions, SeasonKind,
+    },
+    simulation::{Board, LevelBoard},
+    spawn::level::{MarkedTiles, SelectedTile},
+    GameYears, LevelSeed,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Strategy>();
+
+    app.add_systems(
+        OnEnter(Screen::Playing),
+        (reset_demo, spawn_demo_ui).run_if(resource_exists::<Demo>),
+    );
+    app.add_systems(
+        Update,
+        play_demo.run_if(in_state(Pause::Running).and_then(resource_exists::<Demo>)),
+    );
+}
+
+/// Plays each evaluated action this many times with different seeds.
+const SAMPLES: u64 = 8;
+/// Number of partial plans kept by [`Strategy::Beam`].
+const BEAM_WIDTH: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+pub enum Strategy {
+    /// Takes the best action one at a time, assuming no further actions until the end of the game.
+    Greedy,
+    /// Takes the best action one at a time, judged by playouts with random further actions.
+    MonteCarlo,
+    /// Keeps the best few partial plans of the season's actions, judged like [`Strategy::Greedy`].
+    Beam,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 3] = [Strategy::Greedy, Strategy::MonteCarlo, Strategy::Beam];
+
+    /// Translation key of the name.
+    pub fn name_key(&self) -> &'static str {
+        match self {
+            Strategy::Greedy => "strategy-greedy",
+            Strategy::MonteCarlo => "strategy-monte-carlo",
+            Strategy::Beam => "strategy-beam",
+        }
+    }
+
+    /// Tiles for the user actions of `season_kind` in `year`, in the order to use them.
+    /// Plans as many actions as `season_actions` allows in each season of a game lasting `years`.
+    pub fn plan(
+        &self,
+        board: &Board,
+        season_kind: SeasonKind,
+        year: u32,
+        years: u32,
+        season_actions: SeasonActions,
+        rng: &mut impl RngCore,
+    ) -> Vec<TilePos> {
+        // Every candidate is played with the same seeds, so they are compared on equal luck
+        let seeds: Vec<u64> = (0..SAMPLES).map(|_| rng.next_u64()).collect();
+        let mut action_rng = WyRand::seed_from_u64(rng.next_u64());
+        let actions = season_actions.actions(season_kind);
+
+        match self {
+            Strategy::Greedy => {
+                plan_one_by_one(board, season_kind, actions, &mut action_rng, |board| {
+                    average_score(board, &seeds, |board, rng| {
+                        play_rest_of_game(board, season_kind, year, years, rng, |_, _, _| {});
+                    })
+                })
+            }
+            Strategy::MonteCarlo => {
+                plan_one_by_one(board, season_kind, actions, &mut action_rng, |board| {
+                    average_score(board, &seeds, |board, rng| {
+                        play_rest_of_game(
+                            board,
+                            season_kind,
+                            year,
+                            years,
+                            rng,
+                            |board, season_kind, rng| {
+                                random_actions(board, season_kind, season_actions, rng);
+                            },
+                        );
+                    })
+                })
+            }
+            Strategy::Beam => plan_beam(board, season_kind, actions, &mut action_rng, |board| {
+                average_score(board, &seeds, |board, rng| {
+                    play_rest_of_game(board, season_kind, year, years, rng, |_, _, _| {});
+                })
+            }),
+        }
+    }
+}
+
+/// Average score of copies of `board` after `play`, one per seed.
+pub fn average_score(
+    board: &Board,
+    seeds: &[u64],
+    mut play: impl FnMut(&mut Board, &mut WyRand),
+) -> f32 {
+    let total: usize = seeds
+        .iter()
+        .map(|seed| {
+            let mut rng = WyRand::seed_from_u64(*seed);
+            let mut board = board.clone();
+            play(&mut board, &mut rng);
+            board.score
+        })
+        .sum();
+
+    total as f32 / seeds.len() as f32
+}
+
+/// Plays the seasons from `season_kind` in `year` to the end of a game lasting `years`.
+/// Before each later season's simulation, `actions` may use its user actions.
+fn play_rest_of_game(
+    board: &mut Board,
+    season_kind: SeasonKind,
+    year: u32,
+    years: u32,
+    rng: &mut WyRand,
+    mut actions: impl FnMut(&mut Board, SeasonKind, &mut WyRand),
+) {
+    board.simulate(season_kind, rng);
+    board.transition();
+
+    let later_seasons = SeasonKind::ALL
+        .into_iter()
+        .skip_while(|kind| *kind != season_kind)
+        .skip(1)
+        .chain((year + 1..years).flat_map(|_| SeasonKind::ALL));
+    for season_kind in later_seasons {
+        actions(board, season_kind, rng);
+        board.simulate(season_kind, rng);
+        board.transition();
+    }
+}
+
+/// Uses all user actions of `season_kind` on random tiles.
+fn random_actions(
+    board: &mut Board,
+    season_kind: SeasonKind,
+    season_actions: SeasonActions,
+    rng: &mut WyRand,
+) {
+    for _ in 0..season_actions.actions(season_kind) {
+        let tiles = board.user_action_tiles(season_kind);
+        if tiles.is_empty() {
+            return;
+        }
+
+        let tile_pos = tiles[rng.next_u32() as usize % tiles.len()];
+        board.user_action(season_kind, &tile_pos, rng);
+    }
+}
+
+/// Picks each of the `actions` as the best one given the ones before.
+fn plan_one_by_one(
+    board: &Board,
+    season_kind: SeasonKind,
+    actions: usize,
+    rng: &mut WyRand,
+    evaluate: impl Fn(&Board) -> f32,
+) -> Vec<TilePos> {
+    let mut board = board.clone();
+    let mut plan = Vec::new();
+
+    for _ in 0..actions {
+        let best = board
+            .user_action_tiles(season_kind)
+            .into_iter()
+            .filter_map(|tile_pos| {
+                let mut next = board.clone();
+                next.user_action(season_kind, &tile_pos, &mut rng.clone())
+                    .then(|| (evaluate(&next), tile_pos))
+            })
+            .max_by(|(a, _), (b, _)| a.total_cmp(b));
+        let Some((_, tile_pos)) = best else {
+            break;
+        };
+
+        board.user_action(season_kind, &tile_pos, rng);
+        plan.push(tile_pos);
+    }
+
+    plan
+}
+
+/// Expands the [`BEAM_WIDTH`] best partial plans by every action, and keeps the best of those.
+fn plan_beam(
+    board: &Board,
+    season_kind: SeasonKind,
+    actions: usize,
+    rng: &mut WyRand,
+    evaluate: impl Fn(&Board) -> f32,
+) -> Vec<TilePos> {
+    let mut beam = vec![(board.clone(), Vec::new())];
+
+    for _ in 0..actions {
+        let mut candidates: Vec<(f32, Board, Vec<TilePos>)> = Vec::new();
+        for (board, plan) in &beam {
+            for tile_pos in board.user_action_tiles(season_kind) {
+                let mut next = board.clone();
+                if next.user_action(season_kind, &tile_pos, &mut rng.clone()) {
+                    let mut next_plan = plan.clone();
+                    next_plan.push(tile_pos);
+                    candidates.push((evaluate(&next), next, next_plan));
+                }
+            }
+        }
+        if candidates.is_empty() {
+            break;
+        }
+
+        candidates.sort_by(|(a, ..), (b, ..)| b.total_cmp(a));
+        candidates.truncate(BEAM_WIDTH);
+        beam = candidates
+            .into_iter()
+            .map(|(_, board, plan)| (board, plan))
+            .collect();
+    }
+
+    beam.into_iter()
+        .next()
+        .map(|(_, plan)| plan)
+        .unwrap_or_default()
+}
+
+/// Plays a whole game of `years` from `board` with `season_actions` and returns the final score.
+pub fn play_game(
+    strategy: Strategy,
+    board: &Board,
+    years: u32,
+    season_actions: SeasonActions,
+    seed: u64,
+) -> usize {
+    let mut rng = WyRand::seed_from_u64(seed);
+    let mut board = board.clone();
+
+    for year in 0..years {
+        for season_kind in SeasonKind::ALL {
+            let plan = strategy.plan(&board, season_kind, year, years, season_actions, &mut rng);
+            for tile_pos in plan {
+                board.user_action(season_kind, &tile_pos, &mut rng);
+            }
+            board.simulate(season_kind, &mut rng);
+            board.transition();
+        }
+    }
+
+    board.score
+}
+
+/// Seconds between two actions of the demo, so they can be followed.
+const DEMO_ACTION_SECONDS: f32 = 1.0;
+
+/// The computer plays the game while this exists. Started from the title screen.
+#[derive(Resource)]
+pub struct Demo {
+    pub strategy: Strategy,
+    /// Tiles left to act on this season, the next one last.
+    plan: Vec<TilePos>,
+    /// Planning runs in the background, so the game does not stall.
+    planning: Option<Task<Vec<TilePos>>>,
+    planned: bool,
+    timer: Timer,
+}
+
+impl Demo {
+    pub fn new(strategy: Strategy) -> Self {
+        Self {
+            strategy,
+            plan: Vec::new(),
+            planning: None,
+            planned: false,
+            timer: Timer::from_seconds(DEMO_ACTION_SECONDS, TimerMode::Repeating),
+        }
+    }
+}
+
+fn reset_demo(mut demo: ResMut<Demo>) {
+    *demo = Demo::new(demo.strategy);
+}
+
+/// Plans each season in the background, then uses the actions one by one and starts the simulation.
+fn play_demo(
+    mut commands: Commands,
+    mut demo: ResMut<Demo>,
+    time: Res<Time>,
+    season: Res<Season>,
+    level_board: LevelBoard,
+    level_seed: Res<LevelSeed>,
+    season_actions: Res<SeasonActions>,
+    game_years: Res<GameYears>,
+    mut selected_tile: ResMut<SelectedTile>,
+    mut marked_tiles: ResMut<MarkedTiles>,
+    mut next_season_state_events: EventWriter<NextSeasonState>,
+) {
+    if !matches!(season.state, SeasonState::UserInput) {
+        demo.planned = false;
+        marked_tiles.set_if_neq(MarkedTiles::default());
+        return;
+    }
+
+    if let Some(task) = &mut demo.planning {
+        let Some(mut plan) = block_on(future::poll_once(task)) else {
+            return;
+        };
+        plan.reverse();
+        demo.plan = plan;
+        demo.planning = None;
+    }
+
+    // Shows the plan, and keeps the player from acting elsewhere
+    marked_tiles.set_if_neq(MarkedTiles {
+        tiles: demo.plan.clone(),
+        exclusive: !demo.plan.is_empty(),
+    });
+
+    if !demo.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    if season.user_action_resource > 0 {
+        if let Some(tile_pos) = demo.plan.pop() {
+            selected_tile.0 = Some(tile_pos);
+            season.kind.user_action(&mut commands);
+            return;
+        }
+
+        if !demo.planned {
+            if let Some(board) = level_board.board() {
+                let strategy = demo.strategy;
+                let (season_kind, year, years, season_actions) =
+                    (season.kind, season.year, game_years.0, *season_actions);
+                let mut rng = WyRand::seed_from_u64(
+                    level_seed.0 ^ (year as u64 * 4 + season_kind.index() as u64),
+                );
+                demo.planning = Some(AsyncComputeTaskPool::get().spawn(async move {
+                    strategy.plan(&board, season_kind, year, years, season_actions, &mut rng)
+                }));
+            }
+            demo.planned = true;
+            return;
+        }
+    }
+
+    // All actions are used, or there was nothing left to act on
+    next_season_state_events.send(NextSeasonState(season.state.next()));
+}
+
+fn spawn_demo_ui(mut commands: Commands, demo: Res<Demo>) {
+    commands
+        .spawn((
+            Name::new("Demo UI"),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Percent(2.0),
+                    left: Percent(22.0),
+                    padding: UiRect::all(Px(10.0)),
+                    ..default()
+                },
+                background_color: BackgroundColor(NODE_BACKGROUND),
+                ..default()
+            },
+            StateScoped(Screen::Playing),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 26.0,
+                        color: BUTTON_TEXT,
+                        ..default()
+                    },
+                ),
+                ScaledText::new(26.0),
+                LocalizedText {
+                    key: "demo-strategy".into(),
+                    args: vec![demo.strategy.name_key().into()],
+                },
+            ));
+        });
+}
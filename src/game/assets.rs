@@ -1,9 +1,12 @@
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
 
+use super::audio::soundtrack::Playlist;
+
 pub(super) fn plugin(_app: &mut App) {}
 
-#[derive(AssetCollection, Resource)]
+/// The default has no images, for running the level headless.
+#[derive(AssetCollection, Resource, Default)]
 pub struct ImageAssets {
     #[asset(path = "images/ground_tileset.png")]
     #[asset(image(sampler = nearest))]
@@ -43,6 +46,17 @@ pub struct SfxAssets {
     pub button_hover: Handle<AudioSource>,
     #[asset(path = "audio/sfx/button_press.ogg")]
     pub button_press: Handle<AudioSource>,
+    // TODO: Dedicated clips for growing, fire, felling and wind
+    #[asset(
+        paths(
+            "audio/sfx/step1.ogg",
+            "audio/sfx/step2.ogg",
+            "audio/sfx/step3.ogg",
+            "audio/sfx/step4.ogg"
+        ),
+        collection(typed)
+    )]
+    pub steps: Vec<Handle<AudioSource>>,
 }
 
 #[derive(AssetCollection, Resource)]
@@ -51,8 +65,6 @@ pub struct SoundtrackAssets {
     pub title: Handle<AudioSource>,
     #[asset(path = "audio/soundtracks/Vindsvept - Fall of the Leaf.ogg")]
     pub credits: Handle<AudioSource>,
-    #[asset(path = "audio/soundtracks/Vindsvept - Woodland Lullaby.ogg")]
-    pub gameplay: Handle<AudioSource>,
-    #[asset(path = "audio/soundtracks/Vindsvept - Season Unending.ogg")]
-    pub gameplay2: Handle<AudioSource>,
+    #[asset(path = "audio/gameplay.playlist")]
+    pub gameplay: Handle<Playlist>,
 }
@@ -0,0 +1,113 @@
+//! Adapts the soundtrack to the simulation: calm while the player picks an action,
+//! tense while fires burn, and a sting when a year's score is tallied.
+
+use std::time::Duration;
+
+use bevy::{audio::Volume, prelude::*};
+
+use crate::{
+    game::season::{
+        logic::{TreeAction, TreeActionKind},
+        state::SeasonState,
+        Season,
+    },
+    screen::Screen,
+    settings::{AudioSettings, VolumeChannel},
+};
+
+use super::soundtrack::SoundtrackMood;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<YearSting>();
+    app.observe(play_year_sting);
+
+    app.add_systems(
+        Update,
+        (
+            update_soundtrack_mood.run_if(in_state(Screen::Playing)),
+            play_year_sting_notes,
+        ),
+    );
+    app.add_systems(OnExit(Screen::Playing), reset_soundtrack_mood);
+}
+
+fn update_soundtrack_mood(
+    season: Res<Season>,
+    tree_action_q: Query<&TreeAction>,
+    mut mood: ResMut<SoundtrackMood>,
+) {
+    let next_mood = if tree_action_q
+        .iter()
+        .any(|tree_action| tree_action.kind() == TreeActionKind::Burning)
+    {
+        SoundtrackMood::Tense
+    } else if matches!(season.state, SeasonState::UserInput) {
+        SoundtrackMood::Calm
+    } else {
+        SoundtrackMood::Normal
+    };
+    mood.set_if_neq(next_mood);
+}
+
+fn reset_soundtrack_mood(mut mood: ResMut<SoundtrackMood>) {
+    *mood = SoundtrackMood::Normal;
+}
+
+/// Rising notes of the sting in Hz, a C major arpeggio.
+const STING_NOTES: [f32; 4] = [523.25, 659.25, 783.99, 1046.5];
+const STING_NOTE_INTERVAL: Duration = Duration::from_millis(120);
+const STING_NOTE_LENGTH: Duration = Duration::from_millis(300);
+/// Share of the sound effect volume. Plain sine tones are loud.
+const STING_VOLUME: f32 = 0.25;
+
+/// Trigger this event to play the sting for a tallied year.
+#[derive(Event, Debug)]
+pub struct PlayYearSting;
+
+/// A sting playing its notes one after another.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+struct YearSting {
+    next_note: usize,
+    timer: Timer,
+}
+
+fn play_year_sting(_trigger: Trigger<PlayYearSting>, mut commands: Commands) {
+    commands.spawn((
+        Name::new("Year Sting"),
+        YearSting {
+            next_note: 0,
+            timer: Timer::new(STING_NOTE_INTERVAL, TimerMode::Repeating),
+        },
+    ));
+}
+
+fn play_year_sting_notes(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    audio_settings: Res<AudioSettings>,
+    mut pitches: ResMut<Assets<Pitch>>,
+    mut sting_q: Query<(Entity, &mut YearSting)>,
+) {
+    for (entity, mut sting) in &mut sting_q {
+        let due = sting.next_note == 0 || sting.timer.tick(time.delta()).just_finished();
+        if !due {
+            continue;
+        }
+
+        let Some(frequency) = STING_NOTES.get(sting.next_note) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+        commands.spawn((
+            PitchBundle {
+                source: pitches.add(Pitch::new(*frequency, STING_NOTE_LENGTH)),
+                settings: PlaybackSettings::DESPAWN.with_volume(Volume::new(
+                    STING_VOLUME * audio_settings.volume(VolumeChannel::Sfx),
+                )),
+            },
+            VolumeChannel::Sfx,
+        ));
+        sting.next_note += 1;
+    }
+}
@@ -1,24 +1,264 @@
-use bevy::{audio::PlaybackMode, prelude::*};
+use bevy::{audio::SpatialScale, ecs::entity::EntityHashSet, prelude::*, utils::HashMap};
+use bevy_ecs_tilemap::prelude::*;
+use bevy_prng::WyRand;
+use rand_core::{RngCore, SeedableRng};
+
+use crate::{
+    game::{
+        assets::SfxAssets,
+        season::{
+            logic::{TreeAction, TreeActionKind},
+            SimulationSpeed,
+        },
+        spawn::{level::TreeLayer, tree::Tree},
+    },
+    screen::Pause,
+    settings::{AudioSettings, VolumeChannel},
+};
 
 pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(GameplaySfx, FireSfx)>();
+    app.init_resource::<QueuedGameplaySfx>();
+    app.insert_resource(SfxRng(WyRand::seed_from_u64(0)));
+
     app.observe(play_sfx);
+    app.add_systems(
+        Update,
+        (play_gameplay_sfx, update_fire_sfx).run_if(resource_exists::<SfxAssets>),
+    );
 }
 
-fn play_sfx(trigger: Trigger<PlaySfx>, mut commands: Commands) {
+fn play_sfx(
+    trigger: Trigger<PlaySfx>,
+    mut commands: Commands,
+    audio_settings: Res<AudioSettings>,
+    mut queued_gameplay_sfx: ResMut<QueuedGameplaySfx>,
+) {
     let sfx = match trigger.event() {
         PlaySfx::Handle(handle) => handle.clone_weak(),
+        PlaySfx::Gameplay(gameplay_sfx) => {
+            queued_gameplay_sfx.0.push((*gameplay_sfx, None));
+            return;
+        }
+        PlaySfx::GameplayAt(gameplay_sfx, tile_pos) => {
+            queued_gameplay_sfx.0.push((*gameplay_sfx, Some(*tile_pos)));
+            return;
+        }
     };
-    commands.spawn(AudioSourceBundle {
-        source: sfx,
-        settings: PlaybackSettings {
-            mode: PlaybackMode::Despawn,
-            ..default()
+    commands.spawn((
+        AudioSourceBundle {
+            source: sfx,
+            settings: VolumeChannel::Sfx
+                .playback_settings(&audio_settings, PlaybackSettings::DESPAWN),
         },
-    });
+        VolumeChannel::Sfx,
+    ));
 }
 
 /// Trigger this event to play a single sound effect.
 #[derive(Event)]
 pub enum PlaySfx {
     Handle(Handle<AudioSource>),
+    /// A random clip of a gameplay sound, unless too many of it are playing already.
+    Gameplay(GameplaySfx),
+    /// Like [`PlaySfx::Gameplay`], but heard from where the tile is, relative to the camera.
+    GameplayAt(GameplaySfx, TilePos),
+}
+
+/// Scales world positions for spatial sound effects. Sounds within about a map radius
+/// of the camera play at full volume and get quieter further away.
+const SPATIAL_SCALE: SpatialScale = SpatialScale::new_2d(1.0 / 180.0);
+
+/// Sounds of the simulation. Each picks a random clip from its pool
+/// and plays it at a slightly random speed, so repeats do not sound the same.
+/// Fire is not one of them, it loops while trees burn, see `FireSfx`.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component)]
+pub enum GameplaySfx {
+    Plant,
+    Grow,
+    Fell,
+    Gust,
+}
+
+impl GameplaySfx {
+    fn clips<'a>(&self, sfx_assets: &'a SfxAssets) -> &'a [Handle<AudioSource>] {
+        match self {
+            GameplaySfx::Plant | GameplaySfx::Fell => &sfx_assets.steps,
+            GameplaySfx::Grow => std::slice::from_ref(&sfx_assets.button_press),
+            GameplaySfx::Gust => std::slice::from_ref(&sfx_assets.button_hover),
+        }
+    }
+
+    /// Range of playback speeds, which also sets the pitch.
+    fn speeds(&self) -> (f32, f32) {
+        match self {
+            GameplaySfx::Plant => (0.9, 1.1),
+            GameplaySfx::Grow => (1.4, 1.8),
+            GameplaySfx::Fell => (0.5, 0.7),
+            GameplaySfx::Gust => (0.3, 0.4),
+        }
+    }
+
+    /// How many copies may play at once. A growing forest
+    /// would otherwise play dozens of overlapping sounds.
+    fn max_playing(&self) -> usize {
+        match self {
+            GameplaySfx::Plant => 2,
+            GameplaySfx::Grow => 3,
+            GameplaySfx::Fell => 3,
+            GameplaySfx::Gust => 1,
+        }
+    }
+}
+
+/// A fire sound looping at the burning tree it belongs to, until the tree stops burning.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+struct FireSfx(Entity);
+
+/// How many burning trees can be heard at once. A big fire would otherwise
+/// play dozens of overlapping loops.
+const MAX_FIRE_SFX: usize = 4;
+
+/// Fire loops a step clip sped up to a crackle.
+const FIRE_SPEEDS: (f32, f32) = (1.6, 2.0);
+
+/// Gameplay sounds triggered since the last frame, with the tile they are heard from.
+/// They are played together, so sounds triggered in the same frame count towards
+/// [`GameplaySfx::max_playing`].
+#[derive(Debug, Default, Resource)]
+struct QueuedGameplaySfx(Vec<(GameplaySfx, Option<TilePos>)>);
+
+/// Picks clips and speeds apart from the game's entropy, so sounds do not change the simulation.
+#[derive(Resource)]
+struct SfxRng(WyRand);
+
+fn play_gameplay_sfx(
+    mut commands: Commands,
+    mut queued_gameplay_sfx: ResMut<QueuedGameplaySfx>,
+    playing_q: Query<&GameplaySfx>,
+    tree_layer_q: Query<(&TilemapGridSize, &TilemapType, &GlobalTransform), With<TreeLayer>>,
+    sfx_assets: Res<SfxAssets>,
+    audio_settings: Res<AudioSettings>,
+    mut rng: ResMut<SfxRng>,
+) {
+    if queued_gameplay_sfx.0.is_empty() {
+        return;
+    }
+
+    let mut playing = HashMap::<GameplaySfx, usize>::new();
+    for gameplay_sfx in &playing_q {
+        *playing.entry(*gameplay_sfx).or_default() += 1;
+    }
+
+    for (gameplay_sfx, tile_pos) in queued_gameplay_sfx.0.drain(..) {
+        let count = playing.entry(gameplay_sfx).or_default();
+        let clips = gameplay_sfx.clips(&sfx_assets);
+        if *count >= gameplay_sfx.max_playing() || clips.is_empty() {
+            continue;
+        }
+        *count += 1;
+
+        let clip = clips[rng.0.next_u32() as usize % clips.len()].clone_weak();
+        let (min_speed, max_speed) = gameplay_sfx.speeds();
+        let speed =
+            min_speed + (max_speed - min_speed) * (rng.0.next_u32() as f32 / u32::MAX as f32);
+        let (settings, transform) = heard_at(
+            PlaybackSettings::DESPAWN.with_speed(speed),
+            tile_pos,
+            &tree_layer_q,
+        );
+
+        commands.spawn((
+            AudioSourceBundle {
+                source: clip,
+                settings: VolumeChannel::Sfx.playback_settings(&audio_settings, settings),
+            },
+            TransformBundle::from_transform(transform),
+            VolumeChannel::Sfx,
+            gameplay_sfx,
+        ));
+    }
+}
+
+/// Makes a sound spatial, heard from where the tile is, if there is a level to place it on.
+fn heard_at(
+    settings: PlaybackSettings,
+    tile_pos: Option<TilePos>,
+    tree_layer_q: &Query<(&TilemapGridSize, &TilemapType, &GlobalTransform), With<TreeLayer>>,
+) -> (PlaybackSettings, Transform) {
+    let mut settings = settings;
+    let mut transform = Transform::default();
+    if let (Some(tile_pos), Ok((grid_size, map_type, map_transform))) =
+        (tile_pos, tree_layer_q.get_single())
+    {
+        settings = settings
+            .with_spatial(true)
+            .with_spatial_scale(SPATIAL_SCALE);
+        transform.translation = map_transform
+            .transform_point(tile_pos.center_in_world(grid_size, map_type).extend(0.0));
+    }
+    (settings, transform)
+}
+
+/// Starts a fire loop for burning trees, up to [`MAX_FIRE_SFX`], and stops loops whose
+/// tree burned down or was put out. Fire is silent while the game or simulation is paused.
+fn update_fire_sfx(
+    mut commands: Commands,
+    fire_sfx_q: Query<(Entity, &FireSfx)>,
+    tree_q: Query<(Entity, &TreeAction, &TilePos), With<Tree>>,
+    tree_layer_q: Query<(&TilemapGridSize, &TilemapType, &GlobalTransform), With<TreeLayer>>,
+    pause: Option<Res<State<Pause>>>,
+    speed: Res<SimulationSpeed>,
+    sfx_assets: Res<SfxAssets>,
+    audio_settings: Res<AudioSettings>,
+    mut rng: ResMut<SfxRng>,
+) {
+    let running = pause.is_some_and(|pause| *pause.get() == Pause::Running)
+        && !matches!(*speed, SimulationSpeed::Paused);
+    let is_burning = |tree: Entity| {
+        running
+            && tree_q
+                .get(tree)
+                .is_ok_and(|(_, action, _)| matches!(action.kind(), TreeActionKind::Burning))
+    };
+
+    let mut heard = EntityHashSet::default();
+    for (entity, fire_sfx) in &fire_sfx_q {
+        if is_burning(fire_sfx.0) {
+            heard.insert(fire_sfx.0);
+        } else {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    let clips = &sfx_assets.steps;
+    for (tree, _, tile_pos) in &tree_q {
+        if heard.len() >= MAX_FIRE_SFX || clips.is_empty() {
+            break;
+        }
+        if !is_burning(tree) || !heard.insert(tree) {
+            continue;
+        }
+
+        let clip = clips[rng.0.next_u32() as usize % clips.len()].clone_weak();
+        let (min_speed, max_speed) = FIRE_SPEEDS;
+        let speed =
+            min_speed + (max_speed - min_speed) * (rng.0.next_u32() as f32 / u32::MAX as f32);
+        let (settings, transform) = heard_at(
+            PlaybackSettings::LOOP.with_speed(speed),
+            Some(*tile_pos),
+            &tree_layer_q,
+        );
+        commands.spawn((
+            AudioSourceBundle {
+                source: clip,
+                settings: VolumeChannel::Sfx.playback_settings(&audio_settings, settings),
+            },
+            TransformBundle::from_transform(transform),
+            VolumeChannel::Sfx,
+            FireSfx(tree),
+        ));
+    }
 }
@@ -1,37 +1,105 @@
-use bevy::{audio::PlaybackMode, prelude::*};
+use std::time::Duration;
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    audio::Volume,
+    prelude::*,
+};
+
+use crate::{
+    game::{
+        assets::SoundtrackAssets,
+        season::{Season, SeasonKind},
+    },
+    settings::{AudioSettings, VolumeChannel},
+};
 
 pub(super) fn plugin(app: &mut App) {
-    app.register_type::<IsSoundtrack>();
+    app.register_type::<(IsSoundtrack, SoundtrackFade, SoundtrackMood)>();
+    app.init_resource::<SoundtrackMood>();
+    app.init_resource::<SoundtrackIntensity>();
+    app.init_asset::<Playlist>();
+    app.init_asset_loader::<PlaylistLoader>();
+
     app.observe(play_soundtrack);
+    app.observe(play_season_soundtrack);
+    app.add_systems(Update, update_soundtracks);
 }
 
+/// How long the previous soundtrack fades out while the next one fades in.
+const CROSSFADE_DURATION: Duration = Duration::from_secs(2);
+
+/// Roughly how many seconds the soundtrack takes to follow a change of [`SoundtrackMood`].
+const MOOD_TRANSITION_SECONDS: f32 = 1.5;
+
 fn play_soundtrack(
     trigger: Trigger<PlaySoundtrack>,
     mut commands: Commands,
-    soundtrack_query: Query<Entity, With<IsSoundtrack>>,
+    soundtrack_query: Query<
+        (Entity, &Handle<AudioSource>, Option<&SoundtrackFade>),
+        With<IsSoundtrack>,
+    >,
 ) {
-    for entity in &soundtrack_query {
-        commands.entity(entity).despawn_recursive();
+    let next_handle = match trigger.event() {
+        PlaySoundtrack::Handle(handle) => Some(handle),
+        PlaySoundtrack::Disable => None,
+    };
+
+    let mut already_playing = false;
+    for (entity, handle, fade) in &soundtrack_query {
+        if matches!(fade, Some(SoundtrackFade::Out(_))) {
+            continue;
+        }
+        if next_handle.is_some_and(|next_handle| next_handle.id() == handle.id()) {
+            already_playing = true;
+        } else {
+            let level = fade.map_or(1.0, SoundtrackFade::level);
+            commands
+                .entity(entity)
+                .insert(SoundtrackFade::fade_out(level));
+        }
     }
 
-    let soundtrack_handle = match trigger.event() {
-        PlaySoundtrack::Handle(handle) => handle.clone_weak(),
-        PlaySoundtrack::Disable => return,
+    let Some(next_handle) = next_handle else {
+        return;
     };
+    if already_playing {
+        return;
+    }
     commands.spawn((
         AudioSourceBundle {
-            source: soundtrack_handle,
-            settings: PlaybackSettings {
-                mode: PlaybackMode::Loop,
-                ..default()
-            },
+            source: next_handle.clone_weak(),
+            // Starts silent, the fade sets the volume
+            settings: PlaybackSettings::LOOP.with_volume(Volume::ZERO),
         },
         IsSoundtrack,
+        SoundtrackFade::fade_in(),
     ));
 }
 
+/// Trigger this event to play the gameplay playlist's soundtrack for the current [`Season`].
+/// Keeps the current soundtrack if no line of the playlist matches.
+#[derive(Event, Debug)]
+pub struct PlaySeasonSoundtrack;
+
+fn play_season_soundtrack(
+    _trigger: Trigger<PlaySeasonSoundtrack>,
+    mut commands: Commands,
+    season: Res<Season>,
+    soundtrack_assets: Res<SoundtrackAssets>,
+    playlists: Res<Assets<Playlist>>,
+) {
+    if let Some(track) = playlists
+        .get(&soundtrack_assets.gameplay)
+        .and_then(|playlist| playlist.track(season.year, season.kind))
+    {
+        commands.trigger(PlaySoundtrack::Handle(track.clone_weak()));
+    }
+}
+
 /// Trigger this event to play or disable the soundtrack.
-/// Playing a new soundtrack will overwrite the previous one.
+/// Playing a new soundtrack crossfades from the previous one,
+/// playing the current soundtrack again keeps it going.
 /// Soundtracks will loop.
 #[derive(Event)]
 pub enum PlaySoundtrack {
@@ -40,6 +108,259 @@ pub enum PlaySoundtrack {
 }
 
 /// Marker component for the soundtrack entity so we can find it later.
+/// Its volume is kept up by [`update_soundtracks`], instead of as a [`VolumeChannel`].
 #[derive(Component, Reflect)]
 #[reflect(Component)]
 struct IsSoundtrack;
+
+/// A soundtrack fading in, or fading out before it is despawned.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+enum SoundtrackFade {
+    In(Timer),
+    Out(Timer),
+}
+
+impl SoundtrackFade {
+    fn fade_in() -> Self {
+        Self::In(Timer::new(CROSSFADE_DURATION, TimerMode::Once))
+    }
+
+    /// Fades out from `level`, so an interrupted fade in does not jump to full volume.
+    fn fade_out(level: f32) -> Self {
+        let mut timer = Timer::new(CROSSFADE_DURATION, TimerMode::Once);
+        timer.set_elapsed(CROSSFADE_DURATION.mul_f32(1.0 - level));
+        Self::Out(timer)
+    }
+
+    /// Share of the music volume the soundtrack is at.
+    fn level(&self) -> f32 {
+        match self {
+            SoundtrackFade::In(timer) => timer.fraction(),
+            SoundtrackFade::Out(timer) => timer.fraction_remaining(),
+        }
+    }
+
+    fn timer_mut(&mut self) -> &mut Timer {
+        match self {
+            SoundtrackFade::In(timer) | SoundtrackFade::Out(timer) => timer,
+        }
+    }
+}
+
+/// How intense the soundtrack plays. Changes are eased in over [`MOOD_TRANSITION_SECONDS`].
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub enum SoundtrackMood {
+    Calm,
+    #[default]
+    Normal,
+    Tense,
+}
+
+impl SoundtrackMood {
+    /// Share of the music volume.
+    fn volume(&self) -> f32 {
+        match self {
+            SoundtrackMood::Calm => 0.6,
+            SoundtrackMood::Normal | SoundtrackMood::Tense => 1.0,
+        }
+    }
+
+    /// Playback speed, which also shifts the pitch.
+    fn speed(&self) -> f32 {
+        match self {
+            SoundtrackMood::Calm | SoundtrackMood::Normal => 1.0,
+            SoundtrackMood::Tense => 1.06,
+        }
+    }
+}
+
+/// Volume share and speed, on their way to those of the [`SoundtrackMood`].
+#[derive(Resource, Debug)]
+struct SoundtrackIntensity {
+    volume: f32,
+    speed: f32,
+}
+
+impl Default for SoundtrackIntensity {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            speed: 1.0,
+        }
+    }
+}
+
+/// Sets the volume and speed of all soundtracks, following fades, the mood and the settings.
+/// Uses real time, so music keeps changing when the game is paused.
+fn update_soundtracks(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    audio_settings: Res<AudioSettings>,
+    mood: Res<SoundtrackMood>,
+    mut intensity: ResMut<SoundtrackIntensity>,
+    mut soundtrack_q: Query<
+        (Entity, Option<&mut SoundtrackFade>, Option<&AudioSink>),
+        With<IsSoundtrack>,
+    >,
+) {
+    let step = (time.delta_seconds() / MOOD_TRANSITION_SECONDS).min(1.0);
+    intensity.volume += (mood.volume() - intensity.volume) * step;
+    intensity.speed += (mood.speed() - intensity.speed) * step;
+    let volume =
+        audio_settings.master * audio_settings.volume(VolumeChannel::Music) * intensity.volume;
+
+    for (entity, fade, sink) in &mut soundtrack_q {
+        let mut level = 1.0;
+        if let Some(mut fade) = fade {
+            // A soundtrack still loading waits with fading in
+            if sink.is_some() || matches!(*fade, SoundtrackFade::Out(_)) {
+                fade.timer_mut().tick(time.delta());
+            }
+            level = fade.level();
+
+            if fade.timer_mut().finished() {
+                match *fade {
+                    SoundtrackFade::In(_) => {
+                        commands.entity(entity).remove::<SoundtrackFade>();
+                    }
+                    SoundtrackFade::Out(_) => commands.entity(entity).despawn_recursive(),
+                }
+            }
+        }
+
+        if let Some(sink) = sink {
+            sink.set_volume(volume * level);
+            sink.set_speed(intensity.speed);
+        }
+    }
+}
+
+/// Soundtracks picked by year and season, read from a `.playlist` file.
+/// Each line is `years season = track`, and the first line matching the current season plays.
+/// Years are a number, `N+` for year N and later, or `*` for every year.
+/// The season is `spring`, `summer`, `autumn`, `winter` or `*`.
+/// Lines starting with `#` are comments.
+#[derive(Debug, Asset, TypePath)]
+pub struct Playlist {
+    entries: Vec<PlaylistEntry>,
+    #[dependency]
+    tracks: Vec<Handle<AudioSource>>,
+}
+
+impl Playlist {
+    /// The soundtrack for a season, if any line matches.
+    pub fn track(&self, year: u32, season: SeasonKind) -> Option<&Handle<AudioSource>> {
+        self.entries
+            .iter()
+            .find(|entry| {
+                entry.years.contains(year)
+                    && entry
+                        .season
+                        .is_none_or(|entry_season| entry_season == season)
+            })
+            .map(|entry| &self.tracks[entry.track])
+    }
+}
+
+#[derive(Debug)]
+struct PlaylistEntry {
+    years: PlaylistYears,
+    season: Option<SeasonKind>,
+    /// Index into [`Playlist::tracks`].
+    track: usize,
+}
+
+#[derive(Debug)]
+enum PlaylistYears {
+    Every,
+    Exactly(u32),
+    From(u32),
+}
+
+impl PlaylistYears {
+    fn parse(source: &str) -> Option<Self> {
+        if source == "*" {
+            Some(Self::Every)
+        } else if let Some(first) = source.strip_suffix('+') {
+            first.parse().ok().map(Self::From)
+        } else {
+            source.parse().ok().map(Self::Exactly)
+        }
+    }
+
+    fn contains(&self, year: u32) -> bool {
+        match self {
+            PlaylistYears::Every => true,
+            PlaylistYears::Exactly(exact_year) => year == *exact_year,
+            PlaylistYears::From(first_year) => year >= *first_year,
+        }
+    }
+}
+
+#[derive(Default)]
+struct PlaylistLoader;
+
+impl AssetLoader for PlaylistLoader {
+    type Asset = Playlist;
+    type Settings = ();
+    type Error = std::io::Error;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut source = String::new();
+        reader.read_to_string(&mut source).await?;
+
+        let mut playlist = Playlist {
+            entries: Vec::new(),
+            tracks: Vec::new(),
+        };
+        for line in source
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        {
+            let invalid_line = || {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("Invalid playlist line: {line}"),
+                )
+            };
+
+            let (condition, path) = line.split_once('=').ok_or_else(invalid_line)?;
+            let (years, season) = condition
+                .trim()
+                .split_once(char::is_whitespace)
+                .ok_or_else(invalid_line)?;
+            let years = PlaylistYears::parse(years).ok_or_else(invalid_line)?;
+            let season = match season.trim() {
+                "*" => None,
+                season => Some(
+                    SeasonKind::ALL
+                        .into_iter()
+                        .find(|kind| kind.header_key() == season)
+                        .ok_or_else(invalid_line)?,
+                ),
+            };
+
+            playlist.entries.push(PlaylistEntry {
+                years,
+                season,
+                track: playlist.tracks.len(),
+            });
+            playlist
+                .tracks
+                .push(load_context.load(path.trim().to_string()));
+        }
+        Ok(playlist)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["playlist"]
+    }
+}
@@ -0,0 +1,311 @@
+//! Camera controls while playing: panning, zooming and fitting the map between the side panels.
+
+use bevy::{
+    input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel},
+    prelude::*,
+    window::{PrimaryWindow, WindowResized},
+};
+use bevy_ecs_tilemap::prelude::*;
+
+use crate::screen::{Pause, Screen};
+
+use super::{
+    gesture::Gesture,
+    spawn::level::{GroundLayer, TileCursorMoved},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<MapView>();
+
+    app.add_systems(
+        Update,
+        (
+            fit_camera_to_map,
+            (
+                pan_camera_mouse,
+                pan_camera_keyboard,
+                pan_camera_touch,
+                zoom_camera_scroll,
+                zoom_camera_pinch,
+                follow_tile_cursor,
+            )
+                .run_if(in_state(Pause::Running)),
+            clamp_camera,
+        )
+            .chain()
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+/// Share of the window width between the left and right UI panels.
+const MAP_VIEWPORT_WIDTH: f32 = 0.6;
+/// Room around the map when it is fitted to the viewport.
+const FIT_MARGIN: f32 = 1.1;
+/// Smallest projection scale, i.e. how far the camera can zoom in.
+const MIN_SCALE: f32 = 0.25;
+/// How far the camera can zoom out, relative to the fitted scale.
+const MAX_SCALE_FACTOR: f32 = 2.0;
+/// Scale change per scroll wheel line.
+const ZOOM_STEP: f32 = 1.1;
+const PIXELS_PER_LINE: f32 = 20.0;
+/// Keyboard panning speed in window pixels per second.
+const PAN_SPEED: f32 = 600.0;
+const PAN_BUTTONS: [MouseButton; 2] = [MouseButton::Right, MouseButton::Middle];
+/// Held to pan with the keys that otherwise move the tile cursor.
+pub const PAN_MODIFIERS: [KeyCode; 2] = [KeyCode::ShiftLeft, KeyCode::ShiftRight];
+/// Share of the map viewport the tile cursor can move in before the camera follows.
+const FOLLOW_AREA: f32 = 0.8;
+
+/// Where the map is, so the camera does not get lost.
+#[derive(Debug, Default, Resource)]
+struct MapView {
+    bounds: Rect,
+    fit_scale: f32,
+}
+
+impl MapView {
+    fn max_scale(&self) -> f32 {
+        (self.fit_scale * MAX_SCALE_FACTOR).max(1.0)
+    }
+}
+
+/// World space rectangle around all tiles of a map.
+fn map_bounds(
+    map_size: &TilemapSize,
+    grid_size: &TilemapGridSize,
+    tile_size: &TilemapTileSize,
+    map_type: &TilemapType,
+    map_transform: &Transform,
+) -> Rect {
+    let corners = [
+        TilePos::new(0, 0),
+        TilePos::new(map_size.x - 1, 0),
+        TilePos::new(0, map_size.y - 1),
+        TilePos::new(map_size.x - 1, map_size.y - 1),
+    ]
+    .map(|tile_pos| tile_pos.center_in_world(grid_size, map_type) + map_transform.translation.xy());
+
+    let bounds = corners.into_iter().fold(
+        Rect::from_center_size(corners[0], Vec2::ZERO),
+        |bounds, corner| bounds.union_point(corner),
+    );
+    let half_tile = Vec2::new(tile_size.x, tile_size.y) / 2.0;
+
+    Rect {
+        min: bounds.min - half_tile,
+        max: bounds.max + half_tile,
+    }
+}
+
+/// Fits the whole map between the side panels whenever the window is resized or a level is spawned.
+fn fit_camera_to_map(
+    mut resized_events: EventReader<WindowResized>,
+    new_map_q: Query<(), Added<GroundLayer>>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    map_q: Query<
+        (
+            &TilemapSize,
+            &TilemapGridSize,
+            &TilemapTileSize,
+            &TilemapType,
+            &Transform,
+        ),
+        With<GroundLayer>,
+    >,
+    mut camera_q: Query<
+        (&mut Transform, &mut OrthographicProjection),
+        (With<Camera2d>, Without<GroundLayer>),
+    >,
+    mut map_view: ResMut<MapView>,
+) {
+    if resized_events.read().count() == 0 && new_map_q.is_empty() {
+        return;
+    }
+
+    let window = window_q.single();
+    let viewport = Vec2::new(window.width() * MAP_VIEWPORT_WIDTH, window.height());
+    if viewport.x <= 0.0 || viewport.y <= 0.0 {
+        return;
+    }
+
+    if let Ok((map_size, grid_size, tile_size, map_type, map_transform)) = map_q.get_single() {
+        let bounds = map_bounds(map_size, grid_size, tile_size, map_type, map_transform);
+        let fit_scale =
+            (bounds.width() / viewport.x).max(bounds.height() / viewport.y) * FIT_MARGIN;
+        *map_view = MapView { bounds, fit_scale };
+
+        // The panels are equally wide, so the map is centered in the window
+        let (mut transform, mut projection) = camera_q.single_mut();
+        transform.translation = bounds.center().extend(transform.translation.z);
+        projection.scale = fit_scale.max(MIN_SCALE);
+    }
+}
+
+fn pan_camera_mouse(
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    mut motion_events: EventReader<MouseMotion>,
+    mut camera_q: Query<(&mut Transform, &OrthographicProjection), With<Camera2d>>,
+) {
+    let delta: Vec2 = motion_events.read().map(|motion| motion.delta).sum();
+
+    if mouse_input.any_pressed(PAN_BUTTONS) && delta != Vec2::ZERO {
+        let (mut transform, projection) = camera_q.single_mut();
+        // Window y points down, world y points up
+        transform.translation.x -= delta.x * projection.scale;
+        transform.translation.y += delta.y * projection.scale;
+    }
+}
+
+fn pan_camera_keyboard(
+    time: Res<Time>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut camera_q: Query<(&mut Transform, &OrthographicProjection), With<Camera2d>>,
+) {
+    if !keyboard_input.any_pressed(PAN_MODIFIERS) {
+        return;
+    }
+
+    let mut direction = Vec2::ZERO;
+    if keyboard_input.any_pressed([KeyCode::ArrowUp, KeyCode::KeyW]) {
+        direction.y += 1.0;
+    }
+    if keyboard_input.any_pressed([KeyCode::ArrowDown, KeyCode::KeyS]) {
+        direction.y -= 1.0;
+    }
+    if keyboard_input.any_pressed([KeyCode::ArrowRight, KeyCode::KeyD]) {
+        direction.x += 1.0;
+    }
+    if keyboard_input.any_pressed([KeyCode::ArrowLeft, KeyCode::KeyA]) {
+        direction.x -= 1.0;
+    }
+
+    if direction != Vec2::ZERO {
+        let (mut transform, projection) = camera_q.single_mut();
+        let delta = direction.normalize() * PAN_SPEED * projection.scale * time.delta_seconds();
+        transform.translation += delta.extend(0.0);
+    }
+}
+
+/// Pans just enough to keep the tile cursor in view.
+fn follow_tile_cursor(
+    mut tile_cursor_events: EventReader<TileCursorMoved>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    map_q: Query<(&TilemapGridSize, &TilemapType, &Transform), With<GroundLayer>>,
+    mut camera_q: Query<
+        (&mut Transform, &OrthographicProjection),
+        (With<Camera2d>, Without<GroundLayer>),
+    >,
+) {
+    if let Some(TileCursorMoved(tile_pos)) = tile_cursor_events.read().last() {
+        let window = window_q.single();
+        let (grid_size, map_type, map_transform) = map_q.single();
+        let (mut transform, projection) = camera_q.single_mut();
+
+        let tile_position =
+            tile_pos.center_in_world(grid_size, map_type) + map_transform.translation.xy();
+        let half_area = Vec2::new(window.width() * MAP_VIEWPORT_WIDTH, window.height())
+            * projection.scale
+            * FOLLOW_AREA
+            / 2.0;
+
+        let offset = tile_position - transform.translation.xy();
+        transform.translation += (offset - offset.clamp(-half_area, half_area)).extend(0.0);
+    }
+}
+
+/// Scales the projection by `factor`, keeping the world position under `focus` in place.
+/// `focus` is a position in the window.
+fn zoom(
+    transform: &mut Transform,
+    projection: &mut OrthographicProjection,
+    map_view: &MapView,
+    window: &Window,
+    factor: f32,
+    focus: Option<Vec2>,
+) {
+    let scale = projection.scale;
+    projection.scale = (scale * factor).clamp(MIN_SCALE, map_view.max_scale());
+
+    if let Some(focus) = focus {
+        let offset = Vec2::new(
+            focus.x - window.width() / 2.0,
+            window.height() / 2.0 - focus.y,
+        );
+        transform.translation += (offset * (scale - projection.scale)).extend(0.0);
+    }
+}
+
+fn zoom_camera_scroll(
+    mut wheel_events: EventReader<MouseWheel>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    mut camera_q: Query<(&mut Transform, &mut OrthographicProjection), With<Camera2d>>,
+    map_view: Res<MapView>,
+) {
+    let lines: f32 = wheel_events
+        .read()
+        .map(|wheel| match wheel.unit {
+            MouseScrollUnit::Line => wheel.y,
+            MouseScrollUnit::Pixel => wheel.y / PIXELS_PER_LINE,
+        })
+        .sum();
+
+    if lines != 0.0 {
+        let window = window_q.single();
+        let (mut transform, mut projection) = camera_q.single_mut();
+        zoom(
+            &mut transform,
+            &mut projection,
+            &map_view,
+            window,
+            ZOOM_STEP.powf(-lines),
+            window.cursor_position(),
+        );
+    }
+}
+
+fn pan_camera_touch(
+    mut gesture_events: EventReader<Gesture>,
+    mut camera_q: Query<(&mut Transform, &OrthographicProjection), With<Camera2d>>,
+) {
+    for gesture in gesture_events.read() {
+        if let Gesture::Drag(delta) = gesture {
+            let (mut transform, projection) = camera_q.single_mut();
+            transform.translation.x -= delta.x * projection.scale;
+            transform.translation.y += delta.y * projection.scale;
+        }
+    }
+}
+
+fn zoom_camera_pinch(
+    mut gesture_events: EventReader<Gesture>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    mut camera_q: Query<(&mut Transform, &mut OrthographicProjection), With<Camera2d>>,
+    map_view: Res<MapView>,
+) {
+    for gesture in gesture_events.read() {
+        if let Gesture::Pinch { center, scale } = gesture {
+            let (mut transform, mut projection) = camera_q.single_mut();
+            zoom(
+                &mut transform,
+                &mut projection,
+                &map_view,
+                window_q.single(),
+                1.0 / scale,
+                Some(*center),
+            );
+        }
+    }
+}
+
+/// Keeps the center of the view on the map.
+fn clamp_camera(mut camera_q: Query<&mut Transform, With<Camera2d>>, map_view: Res<MapView>) {
+    if map_view.fit_scale > 0.0 {
+        let mut transform = camera_q.single_mut();
+        let position = transform.translation.xy();
+        let clamped = position.clamp(map_view.bounds.min, map_view.bounds.max);
+
+        if clamped != position {
+            transform.translation = clamped.extend(transform.translation.z);
+        }
+    }
+}
@@ -0,0 +1,217 @@
+//! A daily challenge: everyone gets the same board and seed on the same calendar day.
+//! Only the first attempt of a day is scored. Its result and the streak of days played are saved.
+
+use bevy::{prelude::*, utils::SystemTime};
+use bevy_ecs_tilemap::map::TilemapSize;
+use bevy_prng::WyRand;
+use rand_core::{RngCore, SeedableRng};
+
+use crate::{
+    screen::Screen,
+    storage::{self, Entries},
+};
+
+use super::{
+    ai::Demo,
+    simulation::Board,
+    spawn::level::{CustomLevel, MAP_SIZE},
+    tutorial::Tutorial,
+    LevelSeed, Score,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(Daily, DailyRecord)>();
+    app.insert_resource(DailyRecord::load());
+    app.observe(start_daily);
+
+    app.add_systems(
+        OnEnter(Screen::Playing),
+        begin_daily_attempt.run_if(resource_exists::<Daily>),
+    );
+    app.add_systems(
+        OnEnter(Screen::GameOver),
+        record_daily_result.run_if(resource_exists::<Daily>),
+    );
+    app.add_systems(
+        OnEnter(Screen::Title),
+        leave_daily.run_if(resource_exists::<Daily>),
+    );
+}
+
+const DAILY_FILE: &str = "daily.cfg";
+
+/// Days since 1970-01-01 in UTC.
+pub fn today() -> u32 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |since_epoch| (since_epoch.as_secs() / 86_400) as u32)
+}
+
+/// Year, month and day of `day`, counted like [`today`].
+pub fn calendar_date(day: u32) -> (i64, u32, u32) {
+    // Howard Hinnant's civil_from_days, with eras of 400 years starting in March
+    let days = day as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day_of_month = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month, day_of_month)
+}
+
+/// `day` as `YYYY-MM-DD`.
+pub fn format_date(day: u32) -> String {
+    let (year, month, day_of_month) = calendar_date(day);
+    format!("{year:04}-{month:02}-{day_of_month:02}")
+}
+
+/// The daily challenge is played while this exists. Inserted by [`StartDaily`].
+#[derive(Debug, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct Daily {
+    pub day: u32,
+    /// Only the first attempt of the day counts, restarts and replays do not.
+    pub scored: bool,
+}
+
+impl Daily {
+    /// The board with trees of random stages and the seed of the day.
+    pub fn level(&self) -> (Board, LevelSeed) {
+        let mut rng = WyRand::seed_from_u64(self.day as u64);
+        let board = Board::generate(
+            TilemapSize {
+                x: MAP_SIZE,
+                y: MAP_SIZE,
+            },
+            &mut rng,
+        );
+
+        (board, LevelSeed(rng.next_u64()))
+    }
+}
+
+/// The saved result of the last day played.
+#[derive(Debug, Default, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct DailyRecord {
+    /// The last day with a scored attempt, see [`today`].
+    pub day: Option<u32>,
+    pub score: usize,
+    /// Days in a row with a scored attempt, up to [`DailyRecord::day`].
+    pub streak: u32,
+}
+
+impl DailyRecord {
+    fn load() -> Self {
+        let entries = storage::load(DAILY_FILE);
+        Self {
+            day: storage::get(&entries, "day"),
+            score: storage::get(&entries, "score").unwrap_or(0),
+            streak: storage::get(&entries, "streak").unwrap_or(0),
+        }
+    }
+
+    fn save(&self) {
+        let mut entries = Entries::new();
+        if let Some(day) = self.day {
+            entries.insert("day".into(), day.to_string());
+        }
+        entries.insert("score".into(), self.score.to_string());
+        entries.insert("streak".into(), self.streak.to_string());
+
+        if let Err(error) = storage::save(DAILY_FILE, &entries) {
+            error!("Could not save the daily challenge: {error}");
+        }
+    }
+
+    pub fn played(&self, day: u32) -> bool {
+        self.day == Some(day)
+    }
+
+    /// Streak as of `day`. It is broken once a whole day passed without an attempt.
+    pub fn streak_on(&self, day: u32) -> u32 {
+        match self.day {
+            Some(last_day) if last_day + 1 >= day => self.streak,
+            _ => 0,
+        }
+    }
+}
+
+/// Sets up the daily challenge of today and starts playing it.
+#[derive(Debug, Event)]
+pub struct StartDaily;
+
+fn start_daily(
+    _trigger: Trigger<StartDaily>,
+    mut commands: Commands,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    let daily = Daily {
+        day: today(),
+        scored: false,
+    };
+
+    commands.remove_resource::<Tutorial>();
+    commands.remove_resource::<Demo>();
+    let (board, level_seed) = daily.level();
+    commands.insert_resource(CustomLevel(board));
+    commands.insert_resource(level_seed);
+    commands.insert_resource(daily);
+    next_screen.set(Screen::Playing);
+}
+
+/// The attempt is scored if it is the first of the day. It is recorded right away,
+/// so quitting or restarting does not give another one.
+fn begin_daily_attempt(mut daily: ResMut<Daily>, mut record: ResMut<DailyRecord>) {
+    daily.scored = !record.played(daily.day);
+    if !daily.scored {
+        return;
+    }
+
+    record.streak = record.streak_on(daily.day) + 1;
+    record.day = Some(daily.day);
+    record.score = 0;
+    record.save();
+}
+
+fn record_daily_result(daily: Res<Daily>, score: Res<Score>, mut record: ResMut<DailyRecord>) {
+    if daily.scored && record.played(daily.day) {
+        record.score = score.0;
+        record.save();
+    }
+}
+
+fn leave_daily(mut commands: Commands) {
+    commands.remove_resource::<Daily>();
+    commands.remove_resource::<CustomLevel>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calendar_dates() {
+        assert_eq!(calendar_date(0), (1970, 1, 1));
+        assert_eq!(calendar_date(59), (1970, 3, 1));
+        // Leap days, in a year divisible by 4 and in one divisible by 400
+        assert_eq!(calendar_date(789), (1972, 2, 29));
+        assert_eq!(calendar_date(11_016), (2000, 2, 29));
+        assert_eq!(calendar_date(20_744), (2026, 10, 18));
+    }
+
+    #[test]
+    fn formatted_dates() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(20_744), "2026-10-18");
+    }
+}
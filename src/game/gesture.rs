@@ -0,0 +1,138 @@
+//! Tells touch gestures apart, so a drag across the board does not change the selection.
+
+use std::time::Duration;
+
+use bevy::{
+    input::{touch::Touch, InputSystem},
+    prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_event::<Gesture>();
+
+    app.add_systems(PreUpdate, recognize_gestures.after(InputSystem));
+}
+
+/// How far a touch may move in window pixels and still be a tap or long-press.
+const TAP_SLOP: f32 = 12.0;
+const LONG_PRESS_DURATION: Duration = Duration::from_millis(500);
+
+/// A recognized touch gesture. Positions are in window coordinates.
+#[derive(Debug, Clone, Copy, Event)]
+pub enum Gesture {
+    /// A short touch without moving.
+    Tap(Vec2),
+    /// A touch held in place.
+    LongPress(Vec2),
+    /// The finger of a long-press was lifted.
+    LongPressEnded,
+    /// One finger moving, by this many window pixels.
+    Drag(Vec2),
+    /// Two fingers moving apart (`scale` above 1) or together around `center`.
+    Pinch { center: Vec2, scale: f32 },
+}
+
+#[derive(Debug, Default)]
+enum GestureState {
+    #[default]
+    Idle,
+    /// A single finger is down and has not moved yet.
+    Pressed {
+        id: u64,
+        held: Duration,
+    },
+    Dragging {
+        id: u64,
+    },
+    LongPressing {
+        id: u64,
+    },
+    Pinching,
+    /// The gesture is over, waiting for all fingers to lift.
+    Finished,
+}
+
+fn recognize_gestures(
+    time: Res<Time>,
+    touches: Res<Touches>,
+    mut gesture_events: EventWriter<Gesture>,
+    mut state: Local<GestureState>,
+) {
+    let pressed: Vec<&Touch> = touches.iter().collect();
+
+    if let [first, second, ..] = pressed[..] {
+        if matches!(*state, GestureState::LongPressing { .. }) {
+            gesture_events.send(Gesture::LongPressEnded);
+        }
+        *state = GestureState::Pinching;
+
+        let distance = first.position().distance(second.position());
+        let previous_distance = first
+            .previous_position()
+            .distance(second.previous_position());
+        if distance > 0.0 && previous_distance > 0.0 && distance != previous_distance {
+            gesture_events.send(Gesture::Pinch {
+                center: (first.position() + second.position()) / 2.0,
+                scale: distance / previous_distance,
+            });
+        }
+        return;
+    }
+
+    *state = match *state {
+        GestureState::Idle => match pressed[..] {
+            [touch] if touches.just_pressed(touch.id()) => GestureState::Pressed {
+                id: touch.id(),
+                held: Duration::ZERO,
+            },
+            _ => GestureState::Idle,
+        },
+        GestureState::Pressed { id, held } => {
+            if let Some(touch) = touches.get_pressed(id) {
+                if touch.distance().length() > TAP_SLOP {
+                    // Include the movement so far
+                    gesture_events.send(Gesture::Drag(touch.distance()));
+                    GestureState::Dragging { id }
+                } else if held + time.delta() >= LONG_PRESS_DURATION {
+                    gesture_events.send(Gesture::LongPress(touch.position()));
+                    GestureState::LongPressing { id }
+                } else {
+                    GestureState::Pressed {
+                        id,
+                        held: held + time.delta(),
+                    }
+                }
+            } else {
+                if let Some(touch) = touches.get_released(id) {
+                    gesture_events.send(Gesture::Tap(touch.position()));
+                }
+                GestureState::Idle
+            }
+        }
+        GestureState::Dragging { id } => {
+            if let Some(touch) = touches.get_pressed(id) {
+                if touch.delta() != Vec2::ZERO {
+                    gesture_events.send(Gesture::Drag(touch.delta()));
+                }
+                GestureState::Dragging { id }
+            } else {
+                GestureState::Idle
+            }
+        }
+        GestureState::LongPressing { id } => {
+            if touches.get_pressed(id).is_some() {
+                GestureState::LongPressing { id }
+            } else {
+                gesture_events.send(Gesture::LongPressEnded);
+                GestureState::Idle
+            }
+        }
+        GestureState::Pinching | GestureState::Finished => {
+            if pressed.is_empty() {
+                GestureState::Idle
+            } else {
+                GestureState::Finished
+            }
+        }
+    };
+}
@@ -0,0 +1,164 @@
+//! Suggests a strong move: every user action of the season is tried on a [`Board`],
+//! and the rest of the year is played out by the game's rules.
+
+use bevy::{
+    prelude::*,
+    tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task},
+};
+use bevy_ecs_tilemap::tiles::TilePos;
+
+use crate::screen::Screen;
+
+use super::{
+    ai::{average_score, Demo},
+    season::{state::SeasonState, Season, SeasonKind},
+    simulation::{Board, LevelBoard},
+    spawn::level::MarkedTiles,
+    tutorial::Tutorial,
+    LevelSeed,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Hint>();
+    app.observe(find_hint);
+
+    app.add_systems(
+        Update,
+        (
+            clear_hint.run_if(resource_exists::<Hint>.and_then(resource_changed::<Season>)),
+            cancel_hint_search
+                .run_if(resource_exists::<HintSearch>.and_then(resource_changed::<Season>)),
+            poll_hint_search.run_if(resource_exists::<HintSearch>),
+        )
+            .chain()
+            .run_if(in_state(Screen::Playing)),
+    );
+    app.add_systems(OnExit(Screen::Playing), remove_hint);
+}
+
+/// Number of differently seeded plays each action is averaged over.
+const HINT_SAMPLES: u64 = 16;
+
+/// Looks for the best user action of the current season.
+#[derive(Debug, Event)]
+pub struct FindHint;
+
+/// The best user action found, marked on the map until the board changes.
+#[derive(Debug, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct Hint {
+    pub tile_pos: TilePos,
+    /// Points the rest of the year is expected to bring with this action,
+    /// compared to playing it out without one.
+    pub gain: f32,
+}
+
+/// Tries every action in the background, so large maps do not stall the game.
+#[derive(Resource)]
+struct HintSearch(Task<Option<Hint>>);
+
+fn find_hint(
+    _trigger: Trigger<FindHint>,
+    mut commands: Commands,
+    season: Res<Season>,
+    level_board: LevelBoard,
+    level_seed: Res<LevelSeed>,
+    tutorial: Option<Res<Tutorial>>,
+    demo: Option<Res<Demo>>,
+    hint_search: Option<Res<HintSearch>>,
+) {
+    // The tutorial and the demo mark their own tiles
+    if tutorial.is_some()
+        || demo.is_some()
+        || hint_search.is_some()
+        || !matches!(season.state, SeasonState::UserInput)
+        || season.user_action_resource == 0
+    {
+        return;
+    }
+    let Some(board) = level_board.board() else {
+        return;
+    };
+
+    let (season_kind, seed) = (season.kind, level_seed.0);
+    commands.insert_resource(HintSearch(
+        AsyncComputeTaskPool::get().spawn(async move { best_action(&board, season_kind, seed) }),
+    ));
+}
+
+/// The user action of `season_kind` that brings the most points over the rest of the year.
+fn best_action(board: &Board, season_kind: SeasonKind, seed: u64) -> Option<Hint> {
+    let baseline = expected_points(board, season_kind, None, seed);
+    board
+        .user_action_tiles(season_kind)
+        .into_iter()
+        .map(|tile_pos| {
+            let points = expected_points(board, season_kind, Some(&tile_pos), seed);
+            (tile_pos, points)
+        })
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(tile_pos, points)| Hint {
+            tile_pos,
+            gain: points - baseline,
+        })
+}
+
+/// Shows the hint once the search is done.
+fn poll_hint_search(
+    mut commands: Commands,
+    mut hint_search: ResMut<HintSearch>,
+    mut marked_tiles: ResMut<MarkedTiles>,
+) {
+    let Some(hint) = block_on(future::poll_once(&mut hint_search.0)) else {
+        return;
+    };
+    commands.remove_resource::<HintSearch>();
+
+    if let Some(hint) = hint {
+        marked_tiles.set_if_neq(MarkedTiles {
+            tiles: vec![hint.tile_pos],
+            exclusive: false,
+        });
+        commands.insert_resource(hint);
+    }
+}
+
+/// Average score of the rest of the year after using the action of `season_kind` on `tile_pos`,
+/// or without an action for `None`.
+/// Every action is played with the same seeds, so they are compared on equal luck.
+fn expected_points(
+    board: &Board,
+    season_kind: SeasonKind,
+    tile_pos: Option<&TilePos>,
+    seed: u64,
+) -> f32 {
+    let seeds: Vec<u64> = (0..HINT_SAMPLES)
+        .map(|sample| seed.wrapping_add(sample))
+        .collect();
+
+    average_score(board, &seeds, |board, rng| {
+        if let Some(tile_pos) = tile_pos {
+            board.user_action(season_kind, tile_pos, rng);
+        }
+        board.simulate_rest_of_year(season_kind, rng);
+    })
+}
+
+/// Any action or new season changes the board, so the hint no longer applies.
+fn clear_hint(mut commands: Commands, hint: Res<Hint>, mut marked_tiles: ResMut<MarkedTiles>) {
+    if marked_tiles.tiles == [hint.tile_pos] {
+        *marked_tiles = MarkedTiles::default();
+    }
+    commands.remove_resource::<Hint>();
+}
+
+/// The board changed during the search, so its result would no longer apply.
+/// Dropping the task stops it.
+fn cancel_hint_search(mut commands: Commands) {
+    commands.remove_resource::<HintSearch>();
+}
+
+fn remove_hint(mut commands: Commands) {
+    commands.remove_resource::<Hint>();
+    commands.remove_resource::<HintSearch>();
+}
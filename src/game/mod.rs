@@ -2,39 +2,121 @@
 
 use bevy::prelude::*;
 use bevy_prng::WyRand;
-use bevy_rand::plugin::EntropyPlugin;
-use season::Season;
+use bevy_rand::{plugin::EntropyPlugin, prelude::GlobalEntropy};
+use rand_core::{RngCore, SeedableRng};
+use season::{Season, SeasonActions, SeasonKind};
+use spawn::level::SpawnLevel;
 
 use crate::screen::Screen;
 
+pub mod ai;
 pub mod assets;
 pub mod audio;
+pub mod camera;
+pub mod daily;
+pub mod gesture;
+pub mod hint;
+pub mod puzzle;
 pub mod season;
+pub mod simulation;
 pub mod spawn;
+pub mod tutorial;
 pub mod ui;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins(EntropyPlugin::<WyRand>::default());
+    app.add_plugins(simulation_plugin);
     app.add_plugins((
+        ai::plugin,
         audio::plugin,
         assets::plugin,
+        camera::plugin,
+        daily::plugin,
+        gesture::plugin,
+        hint::plugin,
+        puzzle::plugin,
         spawn::plugin,
         season::plugin,
+        tutorial::plugin,
         ui::plugin,
     ));
+}
+
+/// The rules of the game and the level they play out on, without assets, audio, input or UI.
+/// A level starts when entering [`Screen::Playing`]. Also runs headless on its own,
+/// see [`HeadlessPlugin`](crate::HeadlessPlugin).
+pub(super) fn simulation_plugin(app: &mut App) {
+    app.add_plugins(EntropyPlugin::<WyRand>::default());
+    app.add_plugins((spawn::simulation_plugin, season::simulation_plugin));
 
     app.init_resource::<Score>();
-    app.register_type::<Score>();
+    app.init_resource::<LevelSeed>();
+    app.init_resource::<GameYears>();
+    app.register_type::<(Score, LevelSeed, GameYears)>();
 
+    app.add_systems(OnEnter(Screen::Playing), start_level);
     app.add_systems(Update, game_over.run_if(in_state(Screen::Playing)));
 }
 
+/// Number of years a game lasts.
+pub const YEARS: u32 = 3;
+
+/// Number of years the current game lasts. Puzzles may be shorter than [`YEARS`].
+#[derive(Debug, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct GameYears(pub u32);
+
+impl Default for GameYears {
+    fn default() -> Self {
+        Self(YEARS)
+    }
+}
+
 #[derive(Debug, Default, Resource, Reflect)]
 #[reflect(Resource)]
 pub struct Score(pub usize);
 
-fn game_over(season: Res<Season>, mut next_screen: ResMut<NextState<Screen>>) {
-    if season.year == 3 {
+/// Seeds the game's entropy when a level starts, so restarting plays out the same way.
+#[derive(Debug, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct LevelSeed(pub u64);
+
+impl LevelSeed {
+    pub fn random() -> Self {
+        Self(WyRand::from_entropy().next_u64())
+    }
+}
+
+impl Default for LevelSeed {
+    fn default() -> Self {
+        Self::random()
+    }
+}
+
+fn start_level(
+    mut commands: Commands,
+    level_seed: Res<LevelSeed>,
+    mut rng: ResMut<GlobalEntropy<WyRand>>,
+    mut score: ResMut<Score>,
+    mut season: ResMut<Season>,
+    season_actions: Res<SeasonActions>,
+) {
+    rng.reseed(level_seed.0.to_le_bytes());
+
+    commands.trigger(SpawnLevel);
+
+    *score = Score::default();
+    *season = Season {
+        user_action_resource: season_actions.resource(SeasonKind::Spring),
+        ..default()
+    };
+}
+
+fn game_over(
+    season: Res<Season>,
+    game_years: Res<GameYears>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    if season.year == game_years.0 {
         next_screen.set(Screen::GameOver);
     }
 }
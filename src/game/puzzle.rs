@@ -0,0 +1,356 @@
+//! Handcrafted boards with a fixed seed and fixed action timers, so a puzzle plays out
+//! the same every time. Each puzzle awards up to three stars, and the best result is saved.
+
+use bevy::{prelude::*, ui::Val::*};
+use bevy_ecs_tilemap::{map::TilemapSize, tiles::TilePos};
+
+use crate::{
+    locale::LocalizedText,
+    screen::Screen,
+    storage::{self, Entries},
+    ui::{
+        palette::{BUTTON_TEXT, NODE_BACKGROUND},
+        prelude::*,
+    },
+};
+
+use super::{
+    ai::Demo,
+    season::{logic::ActionTimers, SeasonActions},
+    simulation::Board,
+    spawn::{
+        level::{CustomLevel, Ground, MAP_SIZE, NUTRIENT_SEASONS},
+        tree::Tree,
+    },
+    tutorial::Tutorial,
+    GameYears, LevelSeed, Score,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(ActivePuzzle, PuzzleProgress)>();
+    app.insert_resource(PuzzleProgress::load());
+    app.observe(start_puzzle);
+
+    app.add_systems(
+        OnEnter(Screen::Playing),
+        spawn_puzzle_ui.run_if(resource_exists::<ActivePuzzle>),
+    );
+    app.add_systems(
+        OnEnter(Screen::GameOver),
+        record_stars.run_if(resource_exists::<ActivePuzzle>),
+    );
+    app.add_systems(
+        OnEnter(Screen::Title),
+        leave_puzzle.run_if(resource_exists::<ActivePuzzle>),
+    );
+}
+
+const PUZZLES_FILE: &str = "puzzles.cfg";
+
+/// Seeds every puzzle. Seed dispersal, pest outbreaks and pest spread still draw from it,
+/// so the same seed keeps them the same every time.
+pub const PUZZLE_SEED: u64 = 7;
+
+pub struct Puzzle {
+    /// Translation key of the name. Also the key of the saved stars.
+    pub name_key: &'static str,
+    /// Translation key of what to look out for.
+    pub goal_key: &'static str,
+    /// Rows of tiles from `y = 0`. `.` is empty, `s`, `i`, `m` and `o` are trees from seedling
+    /// to overmature. Upper case trees and `+` are on nutrient soil.
+    tiles: [&'static str; MAP_SIZE as usize],
+    /// User actions in each season, starting in spring.
+    pub actions: [usize; 4],
+    pub years: u32,
+    /// Scores needed for one, two and three stars.
+    pub stars: [usize; 3],
+}
+
+impl Puzzle {
+    pub fn board(&self) -> Board {
+        let mut board = Board::new(TilemapSize {
+            x: MAP_SIZE,
+            y: MAP_SIZE,
+        });
+        board.timers = ActionTimers::Fixed;
+
+        for (y, row) in self.tiles.iter().enumerate() {
+            for (x, tile) in row.chars().enumerate() {
+                let tile_pos = TilePos {
+                    x: x as u32,
+                    y: y as u32,
+                };
+                if tile.is_ascii_uppercase() || tile == '+' {
+                    board.tile_mut(&tile_pos).ground = Ground::Nutrient(NUTRIENT_SEASONS);
+                }
+
+                let tree = match tile.to_ascii_lowercase() {
+                    's' => Tree::Seedling,
+                    'i' => Tree::Immature,
+                    'm' => Tree::Mature,
+                    'o' => Tree::Overmature,
+                    _ => continue,
+                };
+                board.plant(&tile_pos, tree);
+            }
+        }
+
+        board
+    }
+
+    pub fn season_actions(&self) -> SeasonActions {
+        SeasonActions(self.actions)
+    }
+
+    /// Stars earned with `score`, from 0 to 3.
+    pub fn stars_for(&self, score: usize) -> u32 {
+        self.stars
+            .iter()
+            .filter(|stars_score| score >= **stars_score)
+            .count() as u32
+    }
+}
+
+/// Every puzzle lasts one year, with the scores worked out by hand for the fixed timers.
+#[rustfmt::skip]
+pub const PUZZLES: [Puzzle; 3] = [
+    // Three seedlings on the nutrient patch have 2 neighbors each, a fourth would stop all
+    Puzzle {
+        name_key: "puzzle-fertile-corner",
+        goal_key: "puzzle-fertile-corner-goal",
+        tiles: [
+            "........",
+            "........",
+            "..++....",
+            "..++....",
+            "........",
+            "........",
+            "........",
+            "........",
+        ],
+        actions: [4, 0, 0, 0],
+        years: 1,
+        stars: [24, 48, 60],
+    },
+    // The fire has to burn a lone seedling planted in spring, other trees take their group along
+    Puzzle {
+        name_key: "puzzle-sacrifice",
+        goal_key: "puzzle-sacrifice-goal",
+        tiles: [
+            "........",
+            ".mm.....",
+            ".m......",
+            "........",
+            "........",
+            ".....m..",
+            "......m.",
+            "........",
+        ],
+        actions: [1, 1, 0, 0],
+        years: 1,
+        stars: [12, 18, 30],
+    },
+    // Only seedlings on the far edges keep growing next to grown trees, inner ones stop them
+    Puzzle {
+        name_key: "puzzle-spacing",
+        goal_key: "puzzle-spacing-goal",
+        tiles: [
+            "i.i.i.i.",
+            "........",
+            "i.i.i.i.",
+            "........",
+            "i.i.i.i.",
+            "........",
+            "i.i.i.i.",
+            "........",
+        ],
+        actions: [4, 0, 0, 0],
+        years: 1,
+        stars: [100, 112, 120],
+    },
+];
+
+/// The puzzle being played, as an index into [`PUZZLES`].
+#[derive(Debug, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct ActivePuzzle(pub usize);
+
+impl ActivePuzzle {
+    pub fn puzzle(&self) -> &'static Puzzle {
+        &PUZZLES[self.0]
+    }
+}
+
+/// Most stars earned in each of the [`PUZZLES`].
+#[derive(Debug, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct PuzzleProgress(pub Vec<u32>);
+
+impl PuzzleProgress {
+    fn load() -> Self {
+        let entries = storage::load(PUZZLES_FILE);
+        Self(
+            PUZZLES
+                .iter()
+                .map(|puzzle| {
+                    storage::get::<u32>(&entries, puzzle.name_key).map_or(0, |stars| stars.min(3))
+                })
+                .collect(),
+        )
+    }
+
+    fn save(&self) {
+        let entries: Entries = PUZZLES
+            .iter()
+            .zip(&self.0)
+            .map(|(puzzle, stars)| (puzzle.name_key.to_string(), stars.to_string()))
+            .collect();
+
+        if let Err(error) = storage::save(PUZZLES_FILE, &entries) {
+            error!("Could not save the puzzle progress: {error}");
+        }
+    }
+}
+
+/// Sets up the puzzle at this index into [`PUZZLES`] and starts playing it.
+#[derive(Debug, Event)]
+pub struct StartPuzzle(pub usize);
+
+fn start_puzzle(
+    trigger: Trigger<StartPuzzle>,
+    mut commands: Commands,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    let index = trigger.event().0;
+    let puzzle = &PUZZLES[index];
+
+    commands.remove_resource::<Tutorial>();
+    commands.remove_resource::<Demo>();
+    commands.insert_resource(ActivePuzzle(index));
+    commands.insert_resource(CustomLevel(puzzle.board()));
+    commands.insert_resource(puzzle.season_actions());
+    commands.insert_resource(GameYears(puzzle.years));
+    commands.insert_resource(ActionTimers::Fixed);
+    commands.insert_resource(LevelSeed(PUZZLE_SEED));
+    next_screen.set(Screen::Playing);
+}
+
+/// Back on the title screen, the next game is a normal one again.
+fn leave_puzzle(mut commands: Commands) {
+    commands.remove_resource::<ActivePuzzle>();
+    commands.remove_resource::<CustomLevel>();
+    commands.insert_resource(SeasonActions::default());
+    commands.insert_resource(GameYears::default());
+    commands.insert_resource(ActionTimers::default());
+}
+
+fn record_stars(
+    active_puzzle: Res<ActivePuzzle>,
+    score: Res<Score>,
+    mut progress: ResMut<PuzzleProgress>,
+) {
+    let stars = active_puzzle.puzzle().stars_for(score.0);
+    if stars > progress.0[active_puzzle.0] {
+        progress.0[active_puzzle.0] = stars;
+        progress.save();
+    }
+}
+
+fn spawn_puzzle_ui(mut commands: Commands, active_puzzle: Res<ActivePuzzle>) {
+    let puzzle = active_puzzle.puzzle();
+
+    commands
+        .spawn((
+            Name::new("Puzzle UI"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(56.0),
+                    flex_direction: FlexDirection::Column,
+                    position_type: PositionType::Absolute,
+                    top: Percent(2.0),
+                    left: Percent(22.0),
+                    padding: UiRect::all(Px(10.0)),
+                    ..default()
+                },
+                background_color: BackgroundColor(NODE_BACKGROUND),
+                ..default()
+            },
+            StateScoped(Screen::Playing),
+        ))
+        .with_children(|parent| {
+            let texts = [
+                LocalizedText::new(puzzle.goal_key),
+                LocalizedText {
+                    key: "puzzle-stars".into(),
+                    args: puzzle.stars.iter().map(ToString::to_string).collect(),
+                },
+            ];
+            for text in texts {
+                parent.spawn((
+                    TextBundle::from_section(
+                        "",
+                        TextStyle {
+                            font_size: 26.0,
+                            color: BUTTON_TEXT,
+                            ..default()
+                        },
+                    ),
+                    ScaledText::new(26.0),
+                    text,
+                ));
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_prng::WyRand;
+    use rand_core::SeedableRng;
+
+    use crate::game::season::SeasonKind;
+
+    use super::*;
+
+    /// Plays `puzzle` with the user actions of each season and returns the final score.
+    fn play(puzzle: &Puzzle, actions: [&[(u32, u32)]; 4]) -> usize {
+        let mut board = puzzle.board();
+        let mut rng = WyRand::seed_from_u64(PUZZLE_SEED);
+
+        for _ in 0..puzzle.years {
+            for season_kind in SeasonKind::ALL {
+                let season_actions = actions[season_kind.index()];
+                assert!(season_actions.len() <= puzzle.season_actions().actions(season_kind));
+
+                for &(x, y) in season_actions {
+                    assert!(
+                        board.user_action(season_kind, &TilePos { x, y }, &mut rng),
+                        "{season_kind:?} action on ({x}, {y}) did nothing"
+                    );
+                }
+                board.simulate(season_kind, &mut rng);
+                board.transition();
+            }
+        }
+
+        board.score
+    }
+
+    #[test]
+    fn puzzles_can_earn_three_stars() {
+        let solutions: [[&[(u32, u32)]; 4]; 3] = [
+            [&[(2, 2), (3, 2), (2, 3), (6, 6)], &[], &[], &[]],
+            [&[(6, 1)], &[(6, 1)], &[], &[]],
+            [&[(7, 0), (7, 2), (7, 4), (0, 7)], &[], &[], &[]],
+        ];
+
+        for (puzzle, solution) in PUZZLES.iter().zip(solutions) {
+            let score = play(puzzle, solution);
+            assert!(
+                puzzle.stars_for(score) == 3,
+                "{} scored {score}, three stars need {}",
+                puzzle.name_key,
+                puzzle.stars[2]
+            );
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! The effect layer shows what is about to happen to a tree.
+//! Effect tiles persist and are only touched when a [`TreeAction`], [`BadWeather`]
+//! or [`Infested`] is added to or removed from a tree.
+
+use bevy::{
+    ecs::entity::{EntityHashMap, EntityHashSet},
+    prelude::*,
+};
+use bevy_ecs_tilemap::{
+    map::TilemapId,
+    tiles::{TileBundle, TilePos, TileStorage, TileTextureIndex},
+};
+
+use crate::{
+    game::spawn::{level::EffectLayer, tree::Tree},
+    screen::Screen,
+};
+
+use super::{
+    logic::{Infested, TreeAction, TreeActionKind},
+    BadWeather,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<EffectTile>();
+    app.init_resource::<InsertedTreeActions>();
+    app.observe(track_inserted_tree_actions);
+
+    app.add_systems(
+        Update,
+        (update_effects, animate_effects)
+            .chain()
+            .run_if(in_state(Screen::Playing)),
+    );
+}
+
+const EFFECT_FRAME_SECONDS: f32 = 0.25;
+
+/// The effects a tree can show.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+pub enum Effect {
+    TreeAction(TreeActionKind),
+    BadWeather,
+    Infested,
+}
+
+impl Effect {
+    /// The effect to show for a tree, if any.
+    /// Bad weather hides everything else, pending actions hide pests.
+    fn of(tree_action: Option<&TreeAction>, bad_weather: bool, infested: bool) -> Option<Self> {
+        if bad_weather {
+            Some(Effect::BadWeather)
+        } else if let Some(tree_action) = tree_action {
+            Some(Effect::TreeAction(tree_action.kind()))
+        } else if infested {
+            Some(Effect::Infested)
+        } else {
+            None
+        }
+    }
+
+    /// Texture indices of the animation frames in the effect tileset.
+    fn frames(&self) -> &'static [u32] {
+        match self {
+            Effect::TreeAction(TreeActionKind::Growing) => &[0],
+            Effect::TreeAction(TreeActionKind::Dying) => &[1],
+            Effect::TreeAction(TreeActionKind::Burning) => &[2, 6],
+            Effect::TreeAction(TreeActionKind::Felling) => &[3],
+            Effect::BadWeather => &[4],
+            Effect::TreeAction(TreeActionKind::Infesting) | Effect::Infested => &[5],
+        }
+    }
+}
+
+/// A tile on the [`EffectLayer`].
+#[derive(Debug, Component, Reflect)]
+#[reflect(Component)]
+pub struct EffectTile {
+    effect: Effect,
+    frame: usize,
+    timer: Timer,
+}
+
+impl EffectTile {
+    fn new(effect: Effect) -> Self {
+        Self {
+            effect,
+            frame: 0,
+            timer: Timer::from_seconds(EFFECT_FRAME_SECONDS, TimerMode::Repeating),
+        }
+    }
+
+    fn texture_index(&self) -> TileTextureIndex {
+        TileTextureIndex(self.effect.frames()[self.frame])
+    }
+}
+
+/// Trees that got a [`TreeAction`] since the last update, including ones that replaced another.
+/// `Changed<TreeAction>` would match every pending action, as their timers tick each frame.
+#[derive(Debug, Default, Resource)]
+struct InsertedTreeActions(EntityHashSet);
+
+fn track_inserted_tree_actions(
+    trigger: Trigger<OnInsert, TreeAction>,
+    mut inserted_tree_actions: ResMut<InsertedTreeActions>,
+) {
+    inserted_tree_actions.0.insert(trigger.entity());
+}
+
+fn update_effects(
+    mut commands: Commands,
+    added_tree_q: Query<Entity, (With<Tree>, Or<(Added<BadWeather>, Added<Infested>)>)>,
+    mut inserted_tree_actions: ResMut<InsertedTreeActions>,
+    mut removed_tree_actions: RemovedComponents<TreeAction>,
+    mut removed_bad_weather: RemovedComponents<BadWeather>,
+    mut removed_infested: RemovedComponents<Infested>,
+    tree_q: Query<
+        (
+            &TilePos,
+            Option<&TreeAction>,
+            Has<BadWeather>,
+            Has<Infested>,
+        ),
+        With<Tree>,
+    >,
+    mut effect_layer_q: Query<(Entity, &mut TileStorage, Ref<EffectLayer>)>,
+    mut effect_tile_q: Query<(&mut EffectTile, &mut TileTextureIndex, &TilePos)>,
+    // Effect tile for each tree showing an effect
+    mut effect_tiles: Local<EntityHashMap<Entity>>,
+) {
+    let (tilemap_entity, mut tile_storage, effect_layer) = effect_layer_q.single_mut();
+    if effect_layer.is_added() {
+        // The previous level is gone, and its effect tiles with it
+        effect_tiles.clear();
+    }
+
+    let changed_trees: EntityHashSet = added_tree_q
+        .iter()
+        .chain(inserted_tree_actions.0.drain())
+        .chain(removed_tree_actions.read())
+        .chain(removed_bad_weather.read())
+        .chain(removed_infested.read())
+        .collect();
+
+    for tree_entity in changed_trees {
+        let effect = tree_q.get(tree_entity).ok().and_then(
+            |(tile_pos, tree_action, bad_weather, infested)| {
+                Effect::of(tree_action, bad_weather, infested).map(|effect| (*tile_pos, effect))
+            },
+        );
+
+        match (effect, effect_tiles.get(&tree_entity).copied()) {
+            (Some((_, effect)), Some(effect_tile_entity)) => {
+                if let Ok((mut effect_tile, mut texture_index, _)) =
+                    effect_tile_q.get_mut(effect_tile_entity)
+                {
+                    // A replaced action may show the same effect
+                    if effect_tile.effect != effect {
+                        *effect_tile = EffectTile::new(effect);
+                        *texture_index = effect_tile.texture_index();
+                    }
+                }
+            }
+            (Some((tile_pos, effect)), None) => {
+                commands.entity(tilemap_entity).with_children(|parent| {
+                    let effect_tile = EffectTile::new(effect);
+                    let effect_tile_entity = parent
+                        .spawn((
+                            TileBundle {
+                                position: tile_pos,
+                                texture_index: effect_tile.texture_index(),
+                                tilemap_id: TilemapId(tilemap_entity),
+                                ..default()
+                            },
+                            effect_tile,
+                        ))
+                        .id();
+
+                    tile_storage.set(&tile_pos, effect_tile_entity);
+                    effect_tiles.insert(tree_entity, effect_tile_entity);
+                });
+            }
+            (None, Some(effect_tile_entity)) => {
+                if let Ok((_, _, tile_pos)) = effect_tile_q.get(effect_tile_entity) {
+                    // A new tree on the same tile might already show its own effect
+                    if tile_storage.get(tile_pos) == Some(effect_tile_entity) {
+                        tile_storage.remove(tile_pos);
+                    }
+                }
+                commands.entity(effect_tile_entity).despawn();
+                effect_tiles.remove(&tree_entity);
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+fn animate_effects(
+    time: Res<Time>,
+    mut effect_tile_q: Query<(&mut EffectTile, &mut TileTextureIndex)>,
+) {
+    for (mut effect_tile, mut texture_index) in &mut effect_tile_q {
+        let frame_count = effect_tile.effect.frames().len();
+        if frame_count > 1 && effect_tile.timer.tick(time.delta()).just_finished() {
+            effect_tile.frame = (effect_tile.frame + 1) % frame_count;
+            *texture_index = effect_tile.texture_index();
+        }
+    }
+}
@@ -1,8 +1,9 @@
-use bevy::{ecs::entity::EntityHashMap, prelude::*};
+use std::time::Duration;
+
+use bevy::prelude::*;
 use bevy_ecs_tilemap::{
     helpers::square_grid::neighbors::Neighbors,
-    map::TilemapId,
-    tiles::{TileBundle, TilePos, TileStorage, TileTextureIndex},
+    tiles::{TilePos, TileStorage},
 };
 use bevy_prng::WyRand;
 use bevy_rand::prelude::GlobalEntropy;
@@ -10,43 +11,60 @@ use rand_core::RngCore;
 
 use crate::{
     game::{
+        audio::sfx::{GameplaySfx, PlaySfx},
         spawn::{
-            level::{EffectLayer, Ground, GroundLayer, TreeLayer},
-            tree::{grow_logic, overcrowd_dying_logic, DespawnTree, Tree},
+            level::{Ground, GroundLayer, SeedBank, TreeLayer},
+            tree::{
+                despawn_tree, grow_logic, overcrowd_dying_logic, Age, DropSeed, Health, SpawnTree,
+                Tree,
+            },
         },
         Score,
     },
     screen::Screen,
 };
 
-use super::{state::SeasonState, BadWeather, Season};
+use super::{rules, state::SeasonState, BadWeather, Season, SimulationSpeed};
 
 pub(super) fn plugin(app: &mut App) {
-    app.register_type::<TreeAction>();
+    app.register_type::<(
+        TreeAction,
+        TreeActionCause,
+        Infested,
+        Quarantined,
+        ActionTimers,
+    )>();
+    app.init_resource::<ActionTimers>();
 
     app.observe(setup_growing);
     app.observe(setup_overcrowd_dying);
+    app.observe(setup_old_age_dying);
     app.observe(setup_seedling_dying);
     app.observe(setup_felling);
+    app.observe(setup_nutrient_decay);
+    app.observe(setup_aging);
+    app.observe(setup_infestation);
+    app.observe(setup_seed_dispersal);
+    app.observe(setup_sprouting);
 
     app.observe(grow);
     app.observe(die);
     app.observe(burn);
     app.observe(fell);
+    app.observe(infest);
 
     app.add_systems(
         Update,
-        (
-            handle_tree_action,
-            remove_effects,
-            handle_effects,
-            handle_bad_weather,
-        )
-            .chain()
+        handle_tree_action
+            .in_set(TreeActionSet)
             .run_if(in_state(Screen::Playing)),
     );
 }
 
+/// The system resolving [`TreeAction`]s as their timers finish.
+#[derive(SystemSet, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct TreeActionSet;
+
 // Spring, Summer, Autumn
 #[derive(Debug, Event)]
 pub struct SetupGrowing;
@@ -56,13 +74,19 @@ fn setup_growing(
     mut commands: Commands,
     tree_tile_storage_q: Query<&TileStorage, With<TreeLayer>>,
     tree_q: Query<(Entity, &Tree, &TilePos), Without<TreeAction>>,
+    ground_tile_storage_q: Query<&TileStorage, With<GroundLayer>>,
+    ground_q: Query<&Ground>,
     mut rng: ResMut<GlobalEntropy<WyRand>>,
+    timers: Res<ActionTimers>,
 ) {
     grow_logic(
         &mut commands,
         tree_tile_storage_q.single(),
         tree_q,
+        ground_tile_storage_q.single(),
+        ground_q,
         &mut rng,
+        *timers,
     );
 }
 
@@ -75,16 +99,42 @@ fn setup_overcrowd_dying(
     mut commands: Commands,
     tree_tile_storage_q: Query<&TileStorage, With<TreeLayer>>,
     tree_q: Query<(Entity, &Tree, &TilePos), Without<TreeAction>>,
+    health_q: Query<&mut Health>,
     mut rng: ResMut<GlobalEntropy<WyRand>>,
+    timers: Res<ActionTimers>,
 ) {
     overcrowd_dying_logic(
         &mut commands,
         tree_tile_storage_q.single(),
         tree_q,
+        health_q,
         &mut rng,
+        *timers,
     );
 }
 
+// Spring, Autumn
+#[derive(Debug, Event)]
+pub struct SetupOldAgeDying;
+
+fn setup_old_age_dying(
+    _trigger: Trigger<SetupOldAgeDying>,
+    mut commands: Commands,
+    tree_q: Query<(Entity, &Tree, &Age), Without<TreeAction>>,
+    mut rng: ResMut<GlobalEntropy<WyRand>>,
+    timers: Res<ActionTimers>,
+) {
+    for (entity, tree, age) in &tree_q {
+        if rules::dies_of_old_age(tree, age) {
+            commands.entity(entity).insert(TreeAction::dying(
+                &mut rng,
+                *timers,
+                TreeActionCause::OldAge,
+            ));
+        }
+    }
+}
+
 // Winter
 #[derive(Debug, Event)]
 pub struct SetupSeedlingDying;
@@ -94,10 +144,15 @@ fn setup_seedling_dying(
     mut commands: Commands,
     tree_q: Query<(Entity, &Tree)>,
     mut rng: ResMut<GlobalEntropy<WyRand>>,
+    timers: Res<ActionTimers>,
 ) {
     for (entity, tree) in &tree_q {
-        if matches!(tree, Tree::Seedling) {
-            commands.entity(entity).insert(TreeAction::dying(&mut rng));
+        if rules::dies_of_frost(tree) {
+            commands.entity(entity).insert(TreeAction::dying(
+                &mut rng,
+                *timers,
+                TreeActionCause::Frost,
+            ));
         }
     }
 }
@@ -108,24 +163,143 @@ pub struct SetupFelling;
 fn setup_felling(
     _trigger: Trigger<SetupFelling>,
     mut commands: Commands,
-    tree_q: Query<(Entity, &Tree), Without<BadWeather>>,
+    tree_q: Query<(Entity, &Tree, Has<BadWeather>)>,
     mut rng: ResMut<GlobalEntropy<WyRand>>,
+    timers: Res<ActionTimers>,
 ) {
-    for (entity, tree) in &tree_q {
-        if matches!(tree, Tree::Mature | Tree::Overmature) {
-            commands
-                .entity(entity)
-                .insert(TreeAction::felling(&mut rng));
+    for (entity, tree, bad_weather) in &tree_q {
+        if rules::is_felled(tree, bad_weather) {
+            commands.entity(entity).insert(TreeAction::felling(
+                &mut rng,
+                *timers,
+                TreeActionCause::Harvest,
+            ));
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, Reflect)]
-enum TreeActionKind {
+// Autumn
+#[derive(Debug, Event)]
+pub struct SetupSeedDispersal;
+
+fn setup_seed_dispersal(
+    _trigger: Trigger<SetupSeedDispersal>,
+    tree_q: Query<(&Tree, &TilePos)>,
+    mut drop_seed_events: EventWriter<DropSeed>,
+    mut rng: ResMut<GlobalEntropy<WyRand>>,
+    mut commands: Commands,
+) {
+    if tree_q.iter().any(|(tree, _)| tree.seeds() > 0) {
+        commands.trigger(PlaySfx::Gameplay(GameplaySfx::Gust));
+    }
+
+    let mut trees: Vec<_> = tree_q.iter().collect();
+    trees.sort_by_key(|(_, tile_pos)| rules::tile_order(tile_pos));
+    for (tree, tile_pos) in trees {
+        for _ in 0..tree.seeds() {
+            drop_seed_events.send(DropSeed {
+                position: rules::seed_landing(tile_pos, &mut *rng),
+            });
+        }
+    }
+}
+
+// Spring
+#[derive(Debug, Event)]
+pub struct SetupSprouting;
+
+fn setup_sprouting(
+    _trigger: Trigger<SetupSprouting>,
+    tree_tile_storage_q: Query<&TileStorage, With<TreeLayer>>,
+    mut ground_q: Query<(&TilePos, &Ground, &mut SeedBank)>,
+    mut spawn_tree_events: EventWriter<SpawnTree>,
+    mut rng: ResMut<GlobalEntropy<WyRand>>,
+) {
+    let tree_tile_storage = tree_tile_storage_q.single();
+
+    let mut tiles: Vec<_> = ground_q.iter_mut().collect();
+    tiles.sort_by_key(|(tile_pos, ..)| rules::tile_order(tile_pos));
+    for (tile_pos, ground, mut seed_bank) in tiles {
+        if seed_bank.0 > 0
+            && tree_tile_storage.get(tile_pos).is_none()
+            && rules::sprouts(ground, &mut *rng)
+        {
+            seed_bank.0 -= 1;
+            spawn_tree_events.send(SpawnTree {
+                tile_pos: *tile_pos,
+                tree: Tree::Seedling,
+                use_resource: false,
+            });
+        }
+    }
+}
+
+// Every season
+#[derive(Debug, Event)]
+pub struct SetupNutrientDecay;
+
+fn setup_nutrient_decay(_trigger: Trigger<SetupNutrientDecay>, mut ground_q: Query<&mut Ground>) {
+    for mut ground in &mut ground_q {
+        if matches!(*ground, Ground::Nutrient(_)) {
+            *ground = ground.decayed();
+        }
+    }
+}
+
+// Every season
+#[derive(Debug, Event)]
+pub struct SetupAging;
+
+fn setup_aging(_trigger: Trigger<SetupAging>, mut age_q: Query<&mut Age>) {
+    for mut age in &mut age_q {
+        age.0 += 1;
+    }
+}
+
+// Spring, Autumn
+#[derive(Debug, Event)]
+pub struct SetupInfestation;
+
+/// Marks a tree infested by bark beetles.
+#[derive(Debug, Default, Component, Reflect)]
+pub struct Infested;
+
+/// Marks a tree that has been treated. It can not catch pests anymore.
+#[derive(Debug, Default, Component, Reflect)]
+pub struct Quarantined;
+
+fn setup_infestation(
+    _trigger: Trigger<SetupInfestation>,
+    mut commands: Commands,
+    tree_q: Query<
+        (Entity, &TilePos, &Health, Has<Infested>),
+        (Without<Quarantined>, Without<TreeAction>),
+    >,
+    mut rng: ResMut<GlobalEntropy<WyRand>>,
+    timers: Res<ActionTimers>,
+) {
+    let mut trees: Vec<_> = tree_q.iter().collect();
+    trees.sort_by_key(|(_, tile_pos, ..)| rules::tile_order(tile_pos));
+    for (entity, _, health, infested) in trees {
+        if infested {
+            commands.entity(entity).insert(TreeAction::infesting(
+                &mut rng,
+                *timers,
+                TreeActionCause::Pests,
+            ));
+        } else if rules::catches_pests(health, &mut *rng) {
+            commands.entity(entity).insert(Infested);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum TreeActionKind {
     Growing,
     Dying,
     Burning,
     Felling,
+    Infesting,
 }
 
 impl TreeActionKind {
@@ -135,15 +309,55 @@ impl TreeActionKind {
             TreeActionKind::Dying => commands.trigger(Die(entity)),
             TreeActionKind::Burning => commands.trigger(Burn(entity)),
             TreeActionKind::Felling => commands.trigger(Fell(entity)),
+            TreeActionKind::Infesting => commands.trigger(Infest(entity)),
         }
     }
+}
+
+/// The rule that scheduled a [`TreeAction`].
+#[derive(Debug, Clone, Copy, Reflect)]
+pub enum TreeActionCause {
+    /// Few enough neighbors to grow.
+    Space {
+        neighbor_level: u32,
+    },
+    /// Too many neighbors for too long.
+    Overcrowding {
+        neighbor_level: u32,
+    },
+    OldAge,
+    Frost,
+    Harvest,
+    Fire,
+    FireSpread,
+    Pests,
+}
+
+/// Random time until a tree action resolves, between 1 and 3.9 seconds.
+pub fn action_duration(rng: &mut impl RngCore) -> Duration {
+    Duration::from_millis((rng.next_u32() % 30) as u64 * 100 + 1000)
+}
+
+/// Time until tree actions resolve in puzzles,
+/// so they play out the same every time.
+pub const FIXED_ACTION_DURATION: Duration = Duration::from_millis(2000);
+
+/// How long tree actions take to resolve.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Resource, Reflect)]
+#[reflect(Resource)]
+pub enum ActionTimers {
+    /// See [`action_duration`].
+    #[default]
+    Random,
+    /// Always [`FIXED_ACTION_DURATION`].
+    Fixed,
+}
 
-    fn effect_texture_index(&self) -> u32 {
+impl ActionTimers {
+    pub fn duration(&self, rng: &mut impl RngCore) -> Duration {
         match self {
-            TreeActionKind::Growing => 0,
-            TreeActionKind::Dying => 1,
-            TreeActionKind::Burning => 2,
-            TreeActionKind::Felling => 3,
+            ActionTimers::Random => action_duration(rng),
+            ActionTimers::Fixed => FIXED_ACTION_DURATION,
         }
     }
 }
@@ -152,44 +366,126 @@ impl TreeActionKind {
 pub struct TreeAction {
     kind: TreeActionKind,
     timer: Timer,
+    cause: TreeActionCause,
+    /// How often a burning tree has burned so far.
+    burns: u32,
 }
 
 impl TreeAction {
-    pub fn growing(rng: &mut GlobalEntropy<WyRand>) -> Self {
-        let duration = (rng.next_u32() % 30) as f32 * 0.1 + 1.0;
+    pub fn growing(
+        rng: &mut GlobalEntropy<WyRand>,
+        timers: ActionTimers,
+        cause: TreeActionCause,
+    ) -> Self {
+        let duration = timers.duration(rng);
         Self {
             kind: TreeActionKind::Growing,
-            timer: Timer::from_seconds(duration, TimerMode::Once),
+            timer: Timer::new(duration, TimerMode::Once),
+            cause,
+            burns: 0,
         }
     }
 
-    pub fn dying(rng: &mut GlobalEntropy<WyRand>) -> Self {
-        let duration = (rng.next_u32() % 30) as f32 * 0.1 + 1.0;
+    pub fn dying(
+        rng: &mut GlobalEntropy<WyRand>,
+        timers: ActionTimers,
+        cause: TreeActionCause,
+    ) -> Self {
+        let duration = timers.duration(rng);
         Self {
             kind: TreeActionKind::Dying,
-            timer: Timer::from_seconds(duration, TimerMode::Once),
+            timer: Timer::new(duration, TimerMode::Once),
+            cause,
+            burns: 0,
         }
     }
 
-    pub fn burning(rng: &mut GlobalEntropy<WyRand>) -> Self {
-        let duration = (rng.next_u32() % 30) as f32 * 0.1 + 1.0; // TODO: Needs to be shorter
+    pub fn burning(
+        rng: &mut GlobalEntropy<WyRand>,
+        timers: ActionTimers,
+        cause: TreeActionCause,
+    ) -> Self {
+        let duration = timers.duration(rng); // TODO: Needs to be shorter
         Self {
             kind: TreeActionKind::Burning,
-            timer: Timer::from_seconds(duration, TimerMode::Repeating),
+            timer: Timer::new(duration, TimerMode::Repeating),
+            cause,
+            burns: 0,
         }
     }
 
-    pub fn felling(rng: &mut GlobalEntropy<WyRand>) -> Self {
-        let duration = (rng.next_u32() % 30) as f32 * 0.1 + 1.0;
+    pub fn felling(
+        rng: &mut GlobalEntropy<WyRand>,
+        timers: ActionTimers,
+        cause: TreeActionCause,
+    ) -> Self {
+        let duration = timers.duration(rng);
         Self {
             kind: TreeActionKind::Felling,
-            timer: Timer::from_seconds(duration, TimerMode::Once),
+            timer: Timer::new(duration, TimerMode::Once),
+            cause,
+            burns: 0,
+        }
+    }
+
+    pub fn infesting(
+        rng: &mut GlobalEntropy<WyRand>,
+        timers: ActionTimers,
+        cause: TreeActionCause,
+    ) -> Self {
+        let duration = timers.duration(rng);
+        Self {
+            kind: TreeActionKind::Infesting,
+            timer: Timer::new(duration, TimerMode::Once),
+            cause,
+            burns: 0,
         }
     }
 }
 
 impl TreeAction {
-    fn trigger(&self, commands: &mut Commands, entity: Entity) {
+    /// Scales how fast the action resolves, e.g. for trees growing on nutrient soil.
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        let duration = self.timer.duration().div_f32(speed);
+        self.timer.set_duration(duration);
+        self
+    }
+
+    pub fn kind(&self) -> TreeActionKind {
+        self.kind
+    }
+
+    pub fn cause(&self) -> TreeActionCause {
+        self.cause
+    }
+
+    /// Time left until the action resolves.
+    pub fn remaining(&self) -> Duration {
+        self.timer.remaining()
+    }
+
+    /// Advances the timer. Returns `true` if the action is due.
+    pub fn tick(&mut self, delta: Duration) -> bool {
+        self.timer.tick(delta).just_finished()
+    }
+
+    /// Whether this is a `kind` action whose timer finished this frame.
+    /// Resolving another tree can replace the action first, like fire spreading to it,
+    /// in which case the replacement is not due yet and the old action is skipped.
+    fn is_due(&self, kind: TreeActionKind) -> bool {
+        self.kind == kind && self.timer.finished()
+    }
+
+    /// Counts another burn of a burning tree. Returns how often it has burned.
+    fn burn(&mut self) -> u32 {
+        self.burns += 1;
+        self.burns
+    }
+
+    /// Applies the action to the tree `entity`.
+    /// Growing and infesting leave the tree standing, so their observers remove the action
+    /// and can schedule the next one, like dying of pests.
+    pub fn resolve(&self, commands: &mut Commands, entity: Entity) {
         self.kind.trigger(commands, entity);
     }
 }
@@ -198,17 +494,14 @@ fn handle_tree_action(
     mut commands: Commands,
     season: Res<Season>,
     time: Res<Time>,
+    speed: Res<SimulationSpeed>,
     mut tree_action_q: Query<(Entity, &mut TreeAction)>,
 ) {
     if matches!(season.state, SeasonState::Simulation) {
+        let delta = speed.scale(time.delta());
         for (entity, mut tree_action) in &mut tree_action_q {
-            if tree_action.timer.tick(time.delta()).just_finished() {
-                tree_action.trigger(&mut commands, entity);
-
-                // TODO: Could be nicer, but we only need to remove for grow
-                if matches!(tree_action.kind, TreeActionKind::Growing) {
-                    commands.entity(entity).remove::<TreeAction>();
-                }
+            if tree_action.tick(delta) {
+                tree_action.resolve(&mut commands, entity);
             }
         }
     }
@@ -217,12 +510,22 @@ fn handle_tree_action(
 #[derive(Debug, Event)]
 pub struct Grow(Entity);
 
-fn grow(trigger: Trigger<Grow>, mut tree_q: Query<&mut Tree>) {
+fn grow(
+    trigger: Trigger<Grow>,
+    mut tree_q: Query<(&mut Tree, &TilePos, &TreeAction)>,
+    mut commands: Commands,
+) {
     let entity = trigger.event().0;
 
-    if let Ok(mut tree) = tree_q.get_mut(entity) {
+    if let Ok((mut tree, tile_pos, tree_action)) = tree_q.get_mut(entity) {
+        if !tree_action.is_due(TreeActionKind::Growing) {
+            return;
+        }
+
+        commands.entity(entity).remove::<TreeAction>();
         if let Some(next_tree) = tree.next() {
             *tree = next_tree;
+            commands.trigger(PlaySfx::GameplayAt(GameplaySfx::Grow, *tile_pos));
         }
     }
 }
@@ -232,63 +535,76 @@ pub struct Die(Entity);
 
 fn die(
     trigger: Trigger<Die>,
-    tile_pos_q: Query<&TilePos, With<Tree>>,
-    mut despawn_tree_events: EventWriter<DespawnTree>,
+    tree_q: Query<(&TilePos, &TreeAction), With<Tree>>,
+    mut tree_tile_storage_q: Query<&mut TileStorage, With<TreeLayer>>,
+    mut commands: Commands,
 ) {
     let entity = trigger.event().0;
 
-    if let Ok(tile_pos) = tile_pos_q.get(entity) {
-        despawn_tree_events.send(DespawnTree {
-            tile_pos: *tile_pos,
-        });
+    if let Ok((tile_pos, tree_action)) = tree_q.get(entity) {
+        if tree_action.is_due(TreeActionKind::Dying) {
+            despawn_tree(
+                &mut commands,
+                &mut tree_tile_storage_q.single_mut(),
+                tile_pos,
+            );
+        }
     }
 }
 
 #[derive(Debug, Event)]
 pub struct Burn(Entity);
 
-//TODO: Split up somehow
+/// Burning trees spread the fire to their neighbors the first time and burn down the second.
 fn burn(
     trigger: Trigger<Burn>,
-    mut entity_map: Local<EntityHashMap<usize>>,
-    tree_q: Query<(&Tree, &TilePos)>,
-    tree_tile_storage_q: Query<&TileStorage, With<TreeLayer>>,
+    mut tree_q: Query<(&Tree, &TilePos, Option<&mut TreeAction>)>,
+    mut tree_tile_storage_q: Query<&mut TileStorage, (With<TreeLayer>, Without<GroundLayer>)>,
     mut commands: Commands,
-    mut despawn_tree_events: EventWriter<DespawnTree>,
     mut rng: ResMut<GlobalEntropy<WyRand>>,
+    timers: Res<ActionTimers>,
     mut ground_q: Query<&mut Ground>,
     ground_tile_storage_q: Query<&TileStorage, With<GroundLayer>>,
 ) {
     let entity = trigger.event().0;
 
-    if let Ok((tree, tile_pos)) = tree_q.get(entity) {
-        let counter = entity_map.entry(entity).or_insert(0);
-        *counter += 1;
-
-        if *counter == 1 {
-            // spread fire
-            debug!("Spread fire from {:?}", tile_pos);
-            let tile_storage = tree_tile_storage_q.single();
-            Neighbors::get_square_neighboring_positions(tile_pos, &tile_storage.size, true)
-                .entities(tile_storage)
+    let Ok((tree, tile_pos, Some(mut tree_action))) = tree_q.get_mut(entity) else {
+        return;
+    };
+    if !tree_action.is_due(TreeActionKind::Burning) {
+        return;
+    }
+    let (tree, tile_pos) = (*tree, *tile_pos);
+    let spreads = rules::spreads_fire(tree_action.burn());
+
+    let mut tile_storage = tree_tile_storage_q.single_mut();
+    if spreads {
+        debug!("Spread fire from {:?}", tile_pos);
+        for neighbor in
+            Neighbors::get_square_neighboring_positions(&tile_pos, &tile_storage.size, true)
+                .entities(&tile_storage)
                 .iter()
-                .for_each(|entity| {
-                    commands
-                        .entity(*entity)
-                        .insert(TreeAction::burning(&mut rng)); // TODO: Only do this if they do not already have a burning component
-                });
-        } else {
-            despawn_tree_events.send(DespawnTree {
-                tile_pos: *tile_pos,
-            });
-            entity_map.remove(&entity);
-
-            if matches!(tree, Tree::Mature | Tree::Overmature) {
-                let tile_storage = ground_tile_storage_q.single();
-                if let Some(entity) = tile_storage.get(tile_pos) {
-                    if let Ok(mut ground) = ground_q.get_mut(entity) {
-                        *ground = Ground::Nutrient;
-                    }
+        {
+            let action = tree_q
+                .get(*neighbor)
+                .ok()
+                .and_then(|(_, _, tree_action)| tree_action.map(TreeAction::kind));
+            if rules::catches_fire(action) {
+                commands.entity(*neighbor).insert(TreeAction::burning(
+                    &mut rng,
+                    *timers,
+                    TreeActionCause::FireSpread,
+                ));
+            }
+        }
+    } else {
+        despawn_tree(&mut commands, &mut tile_storage, &tile_pos);
+
+        if let Some(burned_ground) = rules::burned_ground(&tree) {
+            let tile_storage = ground_tile_storage_q.single();
+            if let Some(entity) = tile_storage.get(&tile_pos) {
+                if let Ok(mut ground) = ground_q.get_mut(entity) {
+                    *ground = burned_ground;
                 }
             }
         }
@@ -300,94 +616,80 @@ pub struct Fell(Entity);
 
 fn fell(
     trigger: Trigger<Fell>,
-    tree_q: Query<(&Tree, &TilePos)>,
-    mut despawn_tree_events: EventWriter<DespawnTree>,
+    tree_q: Query<(&Tree, &TilePos, &TreeAction)>,
+    mut tree_tile_storage_q: Query<&mut TileStorage, (With<TreeLayer>, Without<GroundLayer>)>,
     mut score: ResMut<Score>,
     mut ground_q: Query<&mut Ground>,
     ground_tile_storage_q: Query<&TileStorage, With<GroundLayer>>,
+    mut commands: Commands,
 ) {
     let entity = trigger.event().0;
 
-    if let Ok((tree, tile_pos)) = tree_q.get(entity) {
-        despawn_tree_events.send(DespawnTree {
-            tile_pos: *tile_pos,
-        });
+    if let Ok((tree, tile_pos, tree_action)) = tree_q.get(entity) {
+        if !tree_action.is_due(TreeActionKind::Felling) {
+            return;
+        }
 
-        let mut tree_score = tree.score();
+        despawn_tree(
+            &mut commands,
+            &mut tree_tile_storage_q.single_mut(),
+            tile_pos,
+        );
+        commands.trigger(PlaySfx::GameplayAt(GameplaySfx::Fell, *tile_pos));
 
         let tile_storage = ground_tile_storage_q.single();
         if let Some(entity) = tile_storage.get(tile_pos) {
             if let Ok(mut ground) = ground_q.get_mut(entity) {
-                if matches!(*ground, Ground::Nutrient) {
-                    tree_score *= 3;
-                    *ground = Ground::Normal;
-                }
+                score.0 += rules::felling_score(tree, &mut ground);
             }
         }
-
-        score.0 += tree_score;
     }
 }
 
-// Effects
-// TODO: Not ideal this way
-fn remove_effects(
+#[derive(Debug, Event)]
+pub struct Infest(Entity);
+
+fn infest(
+    trigger: Trigger<Infest>,
+    tree_q: Query<(&Tree, &TilePos, &TreeAction)>,
+    healthy_tree_q: Query<&Tree, (Without<Infested>, Without<Quarantined>)>,
+    mut health_q: Query<&mut Health>,
+    tree_tile_storage_q: Query<&TileStorage, With<TreeLayer>>,
     mut commands: Commands,
-    mut effect_tile_storage_q: Query<&mut TileStorage, With<EffectLayer>>,
+    mut rng: ResMut<GlobalEntropy<WyRand>>,
+    timers: Res<ActionTimers>,
 ) {
-    let mut tile_storage = effect_tile_storage_q.single_mut();
+    let entity = trigger.event().0;
 
-    for x in 0..tile_storage.size.x {
-        for y in 0..tile_storage.size.y {
-            let tile_pos = TilePos { x, y };
-            if let Some(entity) = tile_storage.get(&tile_pos) {
-                commands.entity(entity).despawn();
-            }
-            tile_storage.remove(&tile_pos);
+    if let Ok((tree, tile_pos, tree_action)) = tree_q.get(entity) {
+        if !tree_action.is_due(TreeActionKind::Infesting) {
+            return;
         }
-    }
-}
-
-fn handle_effects(
-    tree_q: Query<(&TilePos, &TreeAction), Without<BadWeather>>,
-    mut commands: Commands,
-    mut effect_tile_storage_q: Query<(Entity, &mut TileStorage), With<EffectLayer>>,
-) {
-    let (tile_map_entity, mut tile_storage) = effect_tile_storage_q.single_mut();
-    for (tile_pos, tree_action) in &tree_q {
-        commands.entity(tile_map_entity).with_children(|parent| {
-            let entity = parent
-                .spawn(TileBundle {
-                    position: *tile_pos,
-                    texture_index: TileTextureIndex(tree_action.kind.effect_texture_index()),
-                    tilemap_id: TilemapId(tile_map_entity),
-                    ..default()
-                })
-                .id();
-
-            tile_storage.set(tile_pos, entity);
-        });
-    }
-}
+        commands.entity(entity).remove::<TreeAction>();
+
+        // Pests spread to neighbors of the same stage
+        let tile_storage = tree_tile_storage_q.single();
+        Neighbors::get_square_neighboring_positions(tile_pos, &tile_storage.size, true)
+            .entities(tile_storage)
+            .iter()
+            .for_each(|neighbor| {
+                if healthy_tree_q
+                    .get(*neighbor)
+                    .is_ok_and(|neighbor_tree| rules::spreads_pests(tree, neighbor_tree, &mut *rng))
+                {
+                    debug!("Spread pests from {:?}", tile_pos);
+                    commands.entity(*neighbor).insert(Infested);
+                }
+            });
 
-fn handle_bad_weather(
-    tree_q: Query<&TilePos, With<BadWeather>>,
-    mut commands: Commands,
-    mut effect_tile_storage_q: Query<(Entity, &mut TileStorage), With<EffectLayer>>,
-) {
-    let (tile_map_entity, mut tile_storage) = effect_tile_storage_q.single_mut();
-    for tile_pos in &tree_q {
-        commands.entity(tile_map_entity).with_children(|parent| {
-            let entity = parent
-                .spawn(TileBundle {
-                    position: *tile_pos,
-                    texture_index: TileTextureIndex(4),
-                    tilemap_id: TilemapId(tile_map_entity),
-                    ..default()
-                })
-                .id();
-
-            tile_storage.set(tile_pos, entity);
-        });
+        if let Ok(mut health) = health_q.get_mut(entity) {
+            if health.damage(rules::PEST_DAMAGE) {
+                commands.entity(entity).insert(TreeAction::dying(
+                    &mut rng,
+                    *timers,
+                    TreeActionCause::Pests,
+                ));
+            }
+        }
     }
 }
@@ -1,35 +1,56 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 use bevy_ecs_tilemap::{
-    helpers::square_grid::{
-        neighbors::{Neighbors, SquareDirection},
-        SquarePos,
-    },
-    tiles::{TileStorage, TileTextureIndex},
+    map::TilemapSize,
+    tiles::{TilePos, TileStorage, TileTextureIndex},
 };
 use bevy_prng::WyRand;
 use bevy_rand::prelude::GlobalEntropy;
-use logic::TreeAction;
+use logic::{ActionTimers, Infested, Quarantined, TreeAction, TreeActionCause};
 use state::SeasonState;
 
 use crate::screen::Screen;
 
-use super::spawn::{
-    level::{Ground, SelectedTile, TreeLayer},
-    tree::{SpawnTree, Tree},
+use super::{
+    audio::sfx::{GameplaySfx, PlaySfx},
+    spawn::{
+        level::{EffectLayer, Ground, SelectedTile, TreeLayer},
+        tree::{DropSeed, SpawnTree, Tree},
+    },
 };
 
+pub mod effect;
 pub mod logic;
+pub mod rules;
 pub mod state;
 
 pub(super) fn plugin(app: &mut App) {
+    app.add_plugins(effect::plugin);
+}
+
+/// Seasons, tree actions and the user actions, without anything shown on screen.
+pub(super) fn simulation_plugin(app: &mut App) {
     app.add_plugins((state::plugin, logic::plugin));
-    app.register_type::<(Season, SeasonKind)>();
+    app.register_type::<(
+        Season,
+        SeasonKind,
+        SeasonActions,
+        SimulationSpeed,
+        SeasonTransition,
+    )>();
     app.init_resource::<Season>();
+    app.init_resource::<SeasonActions>();
+    app.init_resource::<SimulationSpeed>();
 
     app.add_systems(
         Update,
         (handle_transition).run_if(in_state(Screen::Playing)),
     );
+    app.add_systems(
+        OnEnter(Screen::Playing),
+        (reset_season_transition, reset_simulation_speed),
+    );
 
     app.observe(spring_user_action);
     app.observe(summer_user_action);
@@ -37,7 +58,7 @@ pub(super) fn plugin(app: &mut App) {
     app.observe(winter_user_action);
 }
 
-#[derive(Clone, Copy, Debug, Reflect)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect)]
 pub enum SeasonKind {
     Spring,
     Summer,
@@ -46,6 +67,14 @@ pub enum SeasonKind {
 }
 
 impl SeasonKind {
+    /// All seasons of a year, starting in spring.
+    pub const ALL: [SeasonKind; 4] = [
+        SeasonKind::Spring,
+        SeasonKind::Summer,
+        SeasonKind::Autumn,
+        SeasonKind::Winter,
+    ];
+
     pub fn next(&self) -> Self {
         match self {
             SeasonKind::Spring => SeasonKind::Summer,
@@ -55,6 +84,16 @@ impl SeasonKind {
         }
     }
 
+    /// Position in the year, from 0 for spring to 3 for winter.
+    pub fn index(&self) -> usize {
+        match self {
+            SeasonKind::Spring => 0,
+            SeasonKind::Summer => 1,
+            SeasonKind::Autumn => 2,
+            SeasonKind::Winter => 3,
+        }
+    }
+
     pub fn texture_index(&self) -> u32 {
         match self {
             SeasonKind::Spring => 0,
@@ -64,21 +103,32 @@ impl SeasonKind {
         }
     }
 
-    pub fn header(&self) -> &'static str {
+    /// Translation key of the season's name.
+    pub fn header_key(&self) -> &'static str {
         match self {
-            SeasonKind::Spring => "Spring",
-            SeasonKind::Summer => "Summer",
-            SeasonKind::Autumn => "Autumn",
-            SeasonKind::Winter => "Winter",
+            SeasonKind::Spring => "spring",
+            SeasonKind::Summer => "summer",
+            SeasonKind::Autumn => "autumn",
+            SeasonKind::Winter => "winter",
         }
     }
 
-    pub fn description(&self) -> &'static str {
+    /// Translation key of what happens in the season and what the player can do.
+    pub fn description_key(&self) -> &'static str {
         match self {
-            SeasonKind::Spring => "Place 4 seedlings in spring and watch them grow.",
-            SeasonKind::Summer => "No trees ever die in summer...\nBut you have to set fire to a tree. Be careful, it spreads quickly. Leaves behind nutrient soil for mature and overmature trees.",
-            SeasonKind::Autumn => "Place a good gust on any tree to let it drop its seeds in a cross pattern one tile away.",
-            SeasonKind::Winter => "Any seedlings will succumb to the cold. Any mature and overmature trees are taken by the local folk for points.\nDirect snow storms to selected trees to keep them around for another time.",
+            SeasonKind::Spring => "spring-description",
+            SeasonKind::Summer => "summer-description",
+            SeasonKind::Autumn => "autumn-description",
+            SeasonKind::Winter => "winter-description",
+        }
+    }
+
+    /// User action resource for `actions` actions, following what each `*UserAction` uses up.
+    pub fn action_resource(&self, actions: usize) -> usize {
+        match self {
+            SeasonKind::Spring => actions,
+            SeasonKind::Summer | SeasonKind::Autumn => actions.min(1),
+            SeasonKind::Winter => actions * 2,
         }
     }
 
@@ -112,39 +162,183 @@ impl Default for Season {
     }
 }
 
+/// Number of user actions in each season, starting in spring.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct SeasonActions(pub [usize; 4]);
+
+impl Default for SeasonActions {
+    fn default() -> Self {
+        Self([4, 1, 1, 2])
+    }
+}
+
+impl SeasonActions {
+    /// Number of user actions that can be used in `season_kind`.
+    /// One summer or autumn action uses up the whole resource, so those seasons allow one at most.
+    pub fn actions(&self, season_kind: SeasonKind) -> usize {
+        let actions = self.0[season_kind.index()];
+        match season_kind {
+            SeasonKind::Summer | SeasonKind::Autumn => actions.min(1),
+            SeasonKind::Spring | SeasonKind::Winter => actions,
+        }
+    }
+
+    /// The user action resource `season_kind` starts with.
+    pub fn resource(&self, season_kind: SeasonKind) -> usize {
+        season_kind.action_resource(self.0[season_kind.index()])
+    }
+}
+
+/// How fast [`TreeAction`]s and season transitions play out.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Resource, Reflect)]
+#[reflect(Resource)]
+pub enum SimulationSpeed {
+    Paused,
+    #[default]
+    Normal,
+    Double,
+    Quadruple,
+}
+
+impl SimulationSpeed {
+    pub const ALL: [SimulationSpeed; 4] = [
+        SimulationSpeed::Paused,
+        SimulationSpeed::Normal,
+        SimulationSpeed::Double,
+        SimulationSpeed::Quadruple,
+    ];
+
+    pub fn factor(&self) -> f32 {
+        match self {
+            SimulationSpeed::Paused => 0.0,
+            SimulationSpeed::Normal => 1.0,
+            SimulationSpeed::Double => 2.0,
+            SimulationSpeed::Quadruple => 4.0,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SimulationSpeed::Paused => "||",
+            SimulationSpeed::Normal => "1x",
+            SimulationSpeed::Double => "2x",
+            SimulationSpeed::Quadruple => "4x",
+        }
+    }
+
+    /// Scales a frame's delta time to simulation time.
+    pub fn scale(&self, delta: Duration) -> Duration {
+        delta.mul_f32(self.factor())
+    }
+}
+
+/// Seconds until the transition reaches the first tile.
+const TRANSITION_DELAY_SECONDS: f32 = 0.1;
+/// Seconds between two diagonals of the map.
+const TRANSITION_STEP_SECONDS: f32 = 0.1;
+/// Large maps get shorter steps, so a transition never takes longer than this.
+const MAX_TRANSITION_SECONDS: f32 = 3.0;
+
+/// A wave running diagonally across the map, changing each tile to the next season.
+/// Tiles on the same diagonal (`x + y`) change at the same time.
+#[derive(Debug, Reflect, Resource)]
+#[reflect(Resource)]
+pub struct SeasonTransition {
+    season_kind: SeasonKind,
+    elapsed: Duration,
+    step: Duration,
+    /// Next diagonal to change.
+    diagonal: u32,
+    diagonals: u32,
+}
+
+impl SeasonTransition {
+    pub fn new(season_kind: SeasonKind, map_size: &TilemapSize) -> Self {
+        let diagonals = map_size.x + map_size.y - 1;
+        let step = TRANSITION_STEP_SECONDS.min(MAX_TRANSITION_SECONDS / diagonals as f32);
+
+        Self {
+            season_kind,
+            elapsed: Duration::ZERO,
+            step: Duration::from_secs_f32(step),
+            diagonal: 0,
+            diagonals,
+        }
+    }
+
+    /// Number of diagonals the wave has reached so far.
+    fn reached(&self) -> u32 {
+        let delay = Duration::from_secs_f32(TRANSITION_DELAY_SECONDS);
+        let reached = match self.elapsed.checked_sub(delay) {
+            Some(elapsed) => (elapsed.as_secs_f32() / self.step.as_secs_f32()) as u32 + 1,
+            None => 0,
+        };
+
+        reached.min(self.diagonals)
+    }
+}
+
 fn handle_transition(
     mut commands: Commands,
     time: Res<Time>,
-    mut transition_timers: Query<(
-        Entity,
-        &mut SeasonTransition,
+    speed: Res<SimulationSpeed>,
+    season_transition: Option<ResMut<SeasonTransition>>,
+    tile_storages: Query<&TileStorage, Without<EffectLayer>>,
+    mut tile_q: Query<(
         &mut TileTextureIndex,
         Option<&Tree>,
         Option<&Ground>,
+        Has<BadWeather>,
     )>,
 ) {
-    for (entity, mut season_transition, mut texture_index, tree, ground) in &mut transition_timers {
-        if season_transition.timer.tick(time.delta()).just_finished() {
-            /* Actually do something interesting, like change texture index */
-            let offset = if let Some(tree) = tree {
-                tree.texture_index_offset()
-            } else if let Some(ground) = ground {
-                ground.texture_index_offset()
-            } else {
-                0
-            };
-            texture_index.0 = season_transition.season_kind.texture_index() + offset;
-
-            commands.entity(entity).remove::<SeasonTransition>();
-            commands.entity(entity).remove::<BadWeather>();
+    if let Some(mut season_transition) = season_transition {
+        season_transition.elapsed += speed.scale(time.delta());
+
+        let reached = season_transition.reached();
+        for diagonal in season_transition.diagonal..reached {
+            for tile_storage in &tile_storages {
+                let size = tile_storage.size;
+                for x in diagonal.saturating_sub(size.y - 1)..=diagonal.min(size.x - 1) {
+                    let tile_pos = TilePos { x, y: diagonal - x };
+                    if let Some(entity) = tile_storage.get(&tile_pos) {
+                        if let Ok((mut texture_index, tree, ground, bad_weather)) =
+                            tile_q.get_mut(entity)
+                        {
+                            /* Actually do something interesting, like change texture index */
+                            let offset = if let Some(tree) = tree {
+                                tree.texture_index_offset()
+                            } else if let Some(ground) = ground {
+                                ground.texture_index_offset()
+                            } else {
+                                0
+                            };
+                            texture_index.0 =
+                                season_transition.season_kind.texture_index() + offset;
+
+                            if bad_weather {
+                                commands.entity(entity).remove::<BadWeather>();
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        season_transition.diagonal = reached;
+
+        if reached == season_transition.diagonals {
+            commands.remove_resource::<SeasonTransition>();
         }
     }
 }
 
-#[derive(Debug, Reflect, Component)]
-struct SeasonTransition {
-    timer: Timer,
-    season_kind: SeasonKind,
+fn reset_season_transition(mut commands: Commands) {
+    commands.remove_resource::<SeasonTransition>();
+}
+
+/// Every game starts at normal speed, even if the last one was paused or sped up.
+fn reset_simulation_speed(mut speed: ResMut<SimulationSpeed>) {
+    *speed = SimulationSpeed::default();
 }
 
 #[derive(Debug, Event)]
@@ -152,10 +346,29 @@ pub struct SpringUserAction;
 
 fn spring_user_action(
     _trigger: Trigger<SpringUserAction>,
+    mut season: ResMut<Season>,
     mut selected_tile: ResMut<SelectedTile>,
     mut spawn_tree_events: EventWriter<SpawnTree>,
+    tree_tile_storage_q: Query<&TileStorage, With<TreeLayer>>,
+    infested_q: Query<(), With<Infested>>,
+    mut commands: Commands,
 ) {
     if let Some(tile_pos) = selected_tile.0 {
+        let tile_storage = tree_tile_storage_q.single();
+
+        // Infested trees are quarantined instead
+        if let Some(entity) = tile_storage.checked_get(&tile_pos) {
+            if infested_q.contains(entity) {
+                commands
+                    .entity(entity)
+                    .remove::<Infested>()
+                    .insert(Quarantined);
+                season.user_action_resource -= 1;
+                selected_tile.0 = None;
+                return;
+            }
+        }
+
         spawn_tree_events.send(SpawnTree {
             tile_pos,
             tree: Tree::Seedling,
@@ -176,13 +389,16 @@ fn summer_user_action(
     mut commands: Commands,
     tree_tile_storage_q: Query<&TileStorage, With<TreeLayer>>,
     mut rng: ResMut<GlobalEntropy<WyRand>>,
+    timers: Res<ActionTimers>,
 ) {
     if let Some(tile_pos) = selected_tile.0 {
         let tile_storage = tree_tile_storage_q.single();
         if let Some(entity) = tile_storage.checked_get(&tile_pos) {
-            commands
-                .entity(entity)
-                .insert(TreeAction::burning(&mut rng));
+            commands.entity(entity).insert(TreeAction::burning(
+                &mut rng,
+                *timers,
+                TreeActionCause::Fire,
+            ));
 
             season.user_action_resource = 0;
             selected_tile.0 = None;
@@ -197,36 +413,20 @@ fn autumn_user_action(
     _trigger: Trigger<AutumnUserAction>,
     mut season: ResMut<Season>,
     mut selected_tile: ResMut<SelectedTile>,
-    mut spawn_tree_events: EventWriter<SpawnTree>,
+    mut drop_seed_events: EventWriter<DropSeed>,
     tree_q: Query<&Tree>,
     tree_tile_storage_q: Query<&TileStorage, With<TreeLayer>>,
+    mut commands: Commands,
 ) {
     if let Some(tile_pos) = selected_tile.0 {
         let tile_storage = tree_tile_storage_q.single();
         if let Some(entity) = tile_storage.checked_get(&tile_pos) {
             if let Ok(tree) = tree_q.get(entity) {
                 if matches!(tree, Tree::Mature | Tree::Overmature) {
-                    let square_pos = SquarePos::from(&tile_pos);
-                    let f = |direction: SquareDirection| {
-                        if direction.is_cardinal() {
-                            square_pos
-                                .offset(&direction)
-                                .offset(&direction)
-                                .as_tile_pos(&tile_storage.size)
-                        } else {
-                            None
-                        }
-                    };
-
-                    Neighbors::from_directional_closure(f)
-                        .iter()
-                        .for_each(|tile_pos| {
-                            spawn_tree_events.send(SpawnTree {
-                                tile_pos: *tile_pos,
-                                tree: Tree::Seedling,
-                                use_resource: false,
-                            });
-                        });
+                    for position in rules::gust_landings(&tile_pos) {
+                        drop_seed_events.send(DropSeed { position });
+                    }
+                    commands.trigger(PlaySfx::GameplayAt(GameplaySfx::Gust, tile_pos));
 
                     season.user_action_resource = 0;
                     selected_tile.0 = None;
@@ -251,6 +451,7 @@ fn winter_user_action(
 
         if let Some(entity) = tile_storage.get(&tile_pos) {
             commands.entity(entity).insert(BadWeather);
+            commands.trigger(PlaySfx::GameplayAt(GameplaySfx::Gust, tile_pos));
             season.user_action_resource -= 2;
             selected_tile.0 = None;
         }
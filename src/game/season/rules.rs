@@ -0,0 +1,168 @@
+//! The rules of the seasons, shared by the game's observers in [`logic`](super::logic)
+//! and the [`Board`](crate::game::simulation::Board) that looks ahead.
+//! Whatever decides what happens to a tree lives here, so both play by the same rules.
+
+use bevy::prelude::*;
+use bevy_ecs_tilemap::tiles::TilePos;
+use rand_core::RngCore;
+
+use crate::game::spawn::{
+    level::{Ground, NUTRIENT_SEASONS},
+    tree::{Age, Health, Tree},
+};
+
+use super::{logic::TreeActionKind, SeasonKind};
+
+/// A step starting a season's simulation, giving trees their [`TreeActionKind`]s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Setup {
+    Sprouting,
+    OldAgeDying,
+    Infestation,
+    Growing,
+    OvercrowdDying,
+    SeedDispersal,
+    SeedlingDying,
+    Felling,
+}
+
+/// The setups of `season_kind`, in the order they run.
+/// Trees that already have an action are skipped by later setups.
+pub fn setups(season_kind: SeasonKind) -> &'static [Setup] {
+    match season_kind {
+        SeasonKind::Spring => &[
+            Setup::Sprouting,
+            Setup::OldAgeDying,
+            Setup::Infestation,
+            Setup::Growing,
+            Setup::OvercrowdDying,
+        ],
+        SeasonKind::Summer => &[Setup::Growing],
+        SeasonKind::Autumn => &[
+            Setup::SeedDispersal,
+            Setup::OldAgeDying,
+            Setup::Infestation,
+            Setup::Growing,
+            Setup::OvercrowdDying,
+        ],
+        SeasonKind::Winter => &[Setup::SeedlingDying, Setup::Felling],
+    }
+}
+
+/// Key to sort trees or tiles into the order the [`Board`](crate::game::simulation::Board)
+/// visits them in, row by row. Rules drawing random numbers visit them in this order,
+/// so the game and the board draw the same numbers for the same tiles.
+pub fn tile_order(tile_pos: &TilePos) -> (u32, u32) {
+    (tile_pos.y, tile_pos.x)
+}
+
+/// Highest neighbor level a tree can still grow with.
+pub const GROWTH_LIMIT: u32 = 2;
+/// Highest neighbor level a tree can live with without losing health.
+pub const CROWDING_LIMIT: u32 = 4;
+/// Health lost per season for each neighbor level above the overcrowding limit.
+pub const CROWDING_DAMAGE: u32 = 35;
+
+/// Whether a tree with `neighbor_level` grows this season.
+pub fn can_grow(tree: &Tree, neighbor_level: u32) -> bool {
+    tree.next().is_some() && neighbor_level <= GROWTH_LIMIT
+}
+
+/// Health a tree with `neighbor_level` loses this season.
+pub fn crowding_damage(neighbor_level: u32) -> u32 {
+    CROWDING_DAMAGE * neighbor_level.saturating_sub(CROWDING_LIMIT)
+}
+
+/// Whether the tree dies of old age this season.
+pub fn dies_of_old_age(tree: &Tree, age: &Age) -> bool {
+    age.is_old(tree)
+}
+
+/// Whether the tree dies of frost in winter.
+pub fn dies_of_frost(tree: &Tree) -> bool {
+    matches!(tree, Tree::Seedling)
+}
+
+/// Whether the tree is felled in winter. Bad weather keeps the lumberjacks away.
+pub fn is_felled(tree: &Tree, bad_weather: bool) -> bool {
+    !bad_weather && matches!(tree, Tree::Mature | Tree::Overmature)
+}
+
+/// Score for felling the tree. Nutrient soil triples it and is used up.
+pub fn felling_score(tree: &Tree, ground: &mut Ground) -> usize {
+    if matches!(ground, Ground::Nutrient(_)) {
+        *ground = Ground::Normal;
+        tree.score() * 3
+    } else {
+        tree.score()
+    }
+}
+
+/// Whether a tree with `action` catches fire from a burning neighbor.
+/// Trees already burning keep burning where they left off.
+pub fn catches_fire(action: Option<TreeActionKind>) -> bool {
+    !matches!(action, Some(TreeActionKind::Burning))
+}
+
+/// Whether a tree burning for the `burns`th time spreads the fire to its neighbors.
+/// It burns down the time after.
+pub fn spreads_fire(burns: u32) -> bool {
+    burns == 1
+}
+
+/// The ground a burned down tree leaves behind, if it changes.
+pub fn burned_ground(tree: &Tree) -> Option<Ground> {
+    matches!(tree, Tree::Mature | Tree::Overmature).then_some(Ground::Nutrient(NUTRIENT_SEASONS))
+}
+
+/// Trees at or below this health are stressed and can catch pests.
+pub const PEST_STRESS_HEALTH: u32 = 50;
+/// Percent chance for a stressed tree to catch pests each season.
+pub const PEST_OUTBREAK_CHANCE: u32 = 25;
+/// Percent chance for pests to spread to each neighbor of the same stage.
+pub const PEST_SPREAD_CHANCE: u32 = 50;
+/// Health an infested tree loses each season.
+pub const PEST_DAMAGE: u32 = 30;
+
+/// Whether a healthy tree catches pests this season.
+pub fn catches_pests(health: &Health, rng: &mut impl RngCore) -> bool {
+    health.0 <= PEST_STRESS_HEALTH && rng.next_u32() % 100 < PEST_OUTBREAK_CHANCE
+}
+
+/// Whether pests spread from an infested `tree` to a healthy `neighbor`.
+pub fn spreads_pests(tree: &Tree, neighbor: &Tree, rng: &mut impl RngCore) -> bool {
+    neighbor == tree && rng.next_u32() % 100 < PEST_SPREAD_CHANCE
+}
+
+/// Directions seeds fly in when trees drop them on their own.
+pub const SEED_DIRECTIONS: [IVec2; 8] = [
+    IVec2::new(1, 0),
+    IVec2::new(1, 1),
+    IVec2::new(0, 1),
+    IVec2::new(-1, 1),
+    IVec2::new(-1, 0),
+    IVec2::new(-1, -1),
+    IVec2::new(0, -1),
+    IVec2::new(1, -1),
+];
+
+/// Where a seed dropped by the tree on `tile_pos` lands, which may be off the map.
+/// Seeds fly one or two tiles in any direction.
+pub fn seed_landing(tile_pos: &TilePos, rng: &mut impl RngCore) -> IVec2 {
+    let direction = SEED_DIRECTIONS[rng.next_u32() as usize % SEED_DIRECTIONS.len()];
+    let distance = (rng.next_u32() % 2) as i32 + 1;
+
+    IVec2::new(tile_pos.x as i32, tile_pos.y as i32) + direction * distance
+}
+
+/// Where the seeds of the autumn user action on `tile_pos` land,
+/// two tiles away in each cardinal direction.
+pub fn gust_landings(tile_pos: &TilePos) -> [IVec2; 4] {
+    let position = IVec2::new(tile_pos.x as i32, tile_pos.y as i32);
+    [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y].map(|direction| position + direction * 2)
+}
+
+/// Whether a stored seed sprouts on a free tile with `ground` this spring.
+pub fn sprouts(ground: &Ground, rng: &mut impl RngCore) -> bool {
+    rng.next_u32() % 100 < ground.sprout_chance()
+}
@@ -1,14 +1,22 @@
 use bevy::prelude::*;
-use bevy_ecs_tilemap::tiles::{TilePos, TileStorage};
+use bevy_ecs_tilemap::tiles::TileStorage;
 
 use crate::{
-    game::{assets::SoundtrackAssets, audio::soundtrack::PlaySoundtrack},
+    game::{
+        audio::{music::PlayYearSting, soundtrack::PlaySeasonSoundtrack},
+        spawn::level::TreeLayer,
+    },
     screen::Screen,
 };
 
 use super::{
-    logic::{SetupFelling, SetupGrowing, SetupOvercrowdDying, SetupSeedlingDying, TreeAction},
-    Season, SeasonKind, SeasonTransition,
+    logic::{
+        SetupAging, SetupFelling, SetupGrowing, SetupInfestation, SetupNutrientDecay,
+        SetupOldAgeDying, SetupOvercrowdDying, SetupSeedDispersal, SetupSeedlingDying,
+        SetupSprouting, TreeAction,
+    },
+    rules::{self, Setup},
+    Season, SeasonActions, SeasonKind, SeasonTransition,
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -87,14 +95,14 @@ fn to_transition(
 fn to_user_input(
     season: Res<Season>,
     mut next_season_state_events: EventWriter<NextSeasonState>,
-    season_transition_q: Query<&SeasonTransition>,
+    season_transition: Option<Res<SeasonTransition>>,
     mut looking: Local<bool>,
 ) {
     if matches!(season.state, SeasonState::Transition) {
-        if *looking && season_transition_q.is_empty() {
+        if *looking && season_transition.is_none() {
             next_season_state_events.send(NextSeasonState(season.state.next()));
             *looking = false;
-        } else if !season_transition_q.is_empty() {
+        } else if season_transition.is_some() {
             *looking = true;
         }
     }
@@ -106,42 +114,32 @@ struct SetupUserInput(SeasonKind);
 fn setup_user_input(
     trigger: Trigger<SetupUserInput>,
     mut season: ResMut<Season>,
+    season_actions: Res<SeasonActions>,
     mut commands: Commands,
-    soundtrack_assets: Res<SoundtrackAssets>,
 ) {
     season.kind = trigger.event().0.next();
     if matches!(season.kind, SeasonKind::Spring) {
         season.year += 1;
-
-        if season.year == 2 {
-            commands.trigger(PlaySoundtrack::Disable);
-            commands.trigger(PlaySoundtrack::Handle(
-                soundtrack_assets.gameplay2.clone_weak(),
-            ));
-        }
     }
-    season.user_action_resource = 4; // TODO: Needs to be different per season, probably
+    season.user_action_resource = season_actions.resource(season.kind);
+
+    commands.trigger(PlaySeasonSoundtrack);
 }
 
 #[derive(Debug, Event)]
 struct SetupSimulation(SeasonKind);
 
 fn setup_simulation(trigger: Trigger<SetupSimulation>, mut commands: Commands) {
-    match trigger.event().0 {
-        SeasonKind::Spring => {
-            commands.trigger(SetupGrowing);
-            commands.trigger(SetupOvercrowdDying);
-        }
-        SeasonKind::Summer => {
-            commands.trigger(SetupGrowing);
-        }
-        SeasonKind::Autumn => {
-            commands.trigger(SetupGrowing);
-            commands.trigger(SetupOvercrowdDying);
-        }
-        SeasonKind::Winter => {
-            commands.trigger(SetupSeedlingDying);
-            commands.trigger(SetupFelling);
+    for setup in rules::setups(trigger.event().0) {
+        match setup {
+            Setup::Sprouting => commands.trigger(SetupSprouting),
+            Setup::OldAgeDying => commands.trigger(SetupOldAgeDying),
+            Setup::Infestation => commands.trigger(SetupInfestation),
+            Setup::Growing => commands.trigger(SetupGrowing),
+            Setup::OvercrowdDying => commands.trigger(SetupOvercrowdDying),
+            Setup::SeedDispersal => commands.trigger(SetupSeedDispersal),
+            Setup::SeedlingDying => commands.trigger(SetupSeedlingDying),
+            Setup::Felling => commands.trigger(SetupFelling),
         }
     }
 }
@@ -152,19 +150,19 @@ struct SetupTransition(SeasonKind);
 fn setup_transition(
     trigger: Trigger<SetupTransition>,
     mut commands: Commands,
-    tile_storages: Query<&TileStorage>,
+    tree_tile_storage_q: Query<&TileStorage, With<TreeLayer>>,
 ) {
-    /* We are adding the SeasonTransition Component with timer based on TilePos to each entity */
-    for tile_storage in &tile_storages {
-        for x in 0..tile_storage.size.x {
-            for y in 0..tile_storage.size.y {
-                if let Some(entity) = tile_storage.get(&TilePos { x, y }) {
-                    commands.entity(entity).insert(SeasonTransition {
-                        timer: Timer::from_seconds(0.1 + 0.1 * (x + y) as f32, TimerMode::Once),
-                        season_kind: trigger.event().0.next(),
-                    });
-                }
-            }
-        }
+    commands.trigger(SetupNutrientDecay);
+    commands.trigger(SetupAging);
+
+    // Winter felling was the last to score this year
+    if matches!(trigger.event().0, SeasonKind::Winter) {
+        commands.trigger(PlayYearSting);
     }
+
+    /* A single wave runs across the map instead of a timer per tile */
+    commands.insert_resource(SeasonTransition::new(
+        trigger.event().0.next(),
+        &tree_tile_storage_q.single().size,
+    ));
 }
@@ -0,0 +1,719 @@
+//! A headless copy of the board that plays out seasons by the game's [`rules`],
+//! but without entities, observers or rendering. Used to look ahead.
+
+use std::{cmp::Reverse, collections::BinaryHeap, time::Duration};
+
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashSet};
+use bevy_ecs_tilemap::{
+    map::TilemapSize,
+    tiles::{TilePos, TileStorage},
+};
+use rand_core::RngCore;
+
+use super::{
+    season::{
+        logic::{ActionTimers, Infested, Quarantined, TreeActionKind},
+        rules::{self, Setup},
+        BadWeather, SeasonKind,
+    },
+    spawn::{
+        level::{starting_trees, Ground, SeedBank, TreeLayer},
+        tree::{Age, Health, LevelGrid, Tree},
+    },
+};
+
+/// A tree on the [`Board`], with everything the tree entity carries.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BoardTree {
+    pub tree: Tree,
+    pub age: Age,
+    pub health: Health,
+    pub infested: bool,
+    pub quarantined: bool,
+    pub bad_weather: bool,
+}
+
+impl BoardTree {
+    pub fn new(tree: Tree) -> Self {
+        Self {
+            tree,
+            ..Default::default()
+        }
+    }
+}
+
+/// A tile of the [`Board`]: its ground, stored seeds and maybe a tree.
+#[derive(Clone, Copy, Debug)]
+pub struct BoardTile {
+    pub ground: Ground,
+    pub seeds: u32,
+    pub tree: Option<BoardTree>,
+}
+
+impl Default for BoardTile {
+    fn default() -> Self {
+        Self {
+            ground: Ground::Normal,
+            seeds: 0,
+            tree: None,
+        }
+    }
+}
+
+/// A pending action on a tree, like the `TreeAction` component.
+#[derive(Clone, Copy, Debug)]
+struct BoardAction {
+    kind: TreeActionKind,
+    duration: Duration,
+    /// How often a burning tree has burned so far.
+    burns: u32,
+    /// Replaced actions are recognized by their id and skipped.
+    id: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct Board {
+    size: TilemapSize,
+    tiles: Vec<BoardTile>,
+    actions: Vec<Option<BoardAction>>,
+    next_action_id: u64,
+    pub timers: ActionTimers,
+    pub score: usize,
+}
+
+impl Board {
+    pub fn new(size: TilemapSize) -> Self {
+        Self {
+            size,
+            tiles: vec![BoardTile::default(); size.count()],
+            actions: vec![None; size.count()],
+            next_action_id: 0,
+            timers: ActionTimers::Random,
+            score: 0,
+        }
+    }
+
+    /// The board a level starts with, like `spawn_level`.
+    pub fn level(size: TilemapSize) -> Self {
+        let mut board = Self::new(size);
+        for tile_pos in starting_trees(size) {
+            board.plant(&tile_pos, Tree::Immature);
+        }
+
+        board
+    }
+
+    /// A board with trees of random stages on about a quarter of the tiles.
+    pub fn generate(size: TilemapSize, rng: &mut impl RngCore) -> Self {
+        const STAGES: [Tree; 4] = [
+            Tree::Seedling,
+            Tree::Immature,
+            Tree::Mature,
+            Tree::Overmature,
+        ];
+
+        let mut board = Self::new(size);
+        for tile in &mut board.tiles {
+            if rng.next_u32().is_multiple_of(4) {
+                let tree = STAGES[rng.next_u32() as usize % STAGES.len()];
+                tile.tree = Some(BoardTree::new(tree));
+            }
+        }
+
+        board
+    }
+
+    pub fn size(&self) -> TilemapSize {
+        self.size
+    }
+
+    pub fn tile(&self, tile_pos: &TilePos) -> &BoardTile {
+        &self.tiles[self.index(tile_pos)]
+    }
+
+    pub fn tile_mut(&mut self, tile_pos: &TilePos) -> &mut BoardTile {
+        let index = self.index(tile_pos);
+        &mut self.tiles[index]
+    }
+
+    /// All trees on the board with their positions.
+    pub fn trees(&self) -> impl Iterator<Item = (TilePos, BoardTree)> + '_ {
+        self.tiles
+            .iter()
+            .enumerate()
+            .filter_map(|(index, tile)| tile.tree.map(|tree| (self.tile_pos(index), tree)))
+    }
+
+    /// Puts a new `tree` on `tile_pos`. Returns `false` if the tile is taken.
+    pub fn plant(&mut self, tile_pos: &TilePos, tree: Tree) -> bool {
+        let tile = self.tile_mut(tile_pos);
+        if tile.tree.is_some() {
+            return false;
+        }
+
+        tile.tree = Some(BoardTree::new(tree));
+        true
+    }
+
+    /// Plays a whole year from spring to winter without any user actions.
+    pub fn simulate_year(&mut self, rng: &mut impl RngCore) {
+        self.simulate_rest_of_year(SeasonKind::Spring, rng);
+    }
+
+    /// Plays the seasons from `season_kind` to the end of winter without any further user actions.
+    pub fn simulate_rest_of_year(&mut self, season_kind: SeasonKind, rng: &mut impl RngCore) {
+        for season_kind in SeasonKind::ALL
+            .into_iter()
+            .skip_while(|kind| *kind != season_kind)
+        {
+            self.simulate(season_kind, rng);
+            self.transition();
+        }
+    }
+
+    /// Tiles the user action of `season_kind` does something on.
+    pub fn user_action_tiles(&self, season_kind: SeasonKind) -> Vec<TilePos> {
+        (0..self.tiles.len())
+            .filter(|index| {
+                let tree = self.tiles[*index].tree;
+                match season_kind {
+                    SeasonKind::Spring => tree.is_none_or(|tree| tree.infested),
+                    SeasonKind::Summer | SeasonKind::Winter => tree.is_some(),
+                    SeasonKind::Autumn => tree
+                        .is_some_and(|tree| matches!(tree.tree, Tree::Mature | Tree::Overmature)),
+                }
+            })
+            .map(|index| self.tile_pos(index))
+            .collect()
+    }
+
+    /// Uses the user action of `season_kind` on `tile_pos`, like the `*UserAction` observers.
+    /// Returns `false` if it does nothing there.
+    pub fn user_action(
+        &mut self,
+        season_kind: SeasonKind,
+        tile_pos: &TilePos,
+        rng: &mut impl RngCore,
+    ) -> bool {
+        let index = self.index(tile_pos);
+        let Some(tree) = &mut self.tiles[index].tree else {
+            return matches!(season_kind, SeasonKind::Spring)
+                && self.plant(tile_pos, Tree::Seedling);
+        };
+
+        match season_kind {
+            // Infested trees are quarantined instead
+            SeasonKind::Spring => {
+                if !tree.infested {
+                    return false;
+                }
+                tree.infested = false;
+                tree.quarantined = true;
+            }
+            SeasonKind::Summer => {
+                self.schedule(index, TreeActionKind::Burning, self.timers.duration(rng));
+            }
+            SeasonKind::Autumn => {
+                if !matches!(tree.tree, Tree::Mature | Tree::Overmature) {
+                    return false;
+                }
+
+                for tile_pos in self.drop_seeds(rules::gust_landings(tile_pos)) {
+                    self.plant(&tile_pos, Tree::Seedling);
+                }
+            }
+            SeasonKind::Winter => tree.bad_weather = true,
+        }
+
+        true
+    }
+
+    /// Plays the simulation of a season until every tree action has resolved.
+    /// Runs the same [`rules::setups`] as the game.
+    pub fn simulate(&mut self, season_kind: SeasonKind, rng: &mut impl RngCore) {
+        let mut sprouts = Vec::new();
+        let mut seeds = Vec::new();
+
+        for setup in rules::setups(season_kind) {
+            match setup {
+                Setup::Sprouting => self.setup_sprouting(&mut sprouts, rng),
+                Setup::OldAgeDying => self.setup_old_age_dying(rng),
+                Setup::Infestation => self.setup_infestation(rng),
+                Setup::Growing => self.setup_growing(rng),
+                Setup::OvercrowdDying => self.setup_overcrowd_dying(rng),
+                Setup::SeedDispersal => self.setup_seed_dispersal(&mut seeds, rng),
+                Setup::SeedlingDying => self.setup_seedling_dying(rng),
+                Setup::Felling => self.setup_felling(rng),
+            }
+        }
+
+        // Seedlings appear before the first action resolves
+        sprouts.extend(self.drop_seeds(seeds));
+        for tile_pos in sprouts {
+            self.plant(&tile_pos, Tree::Seedling);
+        }
+
+        self.resolve_actions(rng);
+    }
+
+    /// Ends a season: nutrient soil decays, trees age and snow storms pass.
+    pub fn transition(&mut self) {
+        for tile in &mut self.tiles {
+            tile.ground = tile.ground.decayed();
+            if let Some(tree) = &mut tile.tree {
+                tree.age.0 += 1;
+                tree.bad_weather = false;
+            }
+        }
+    }
+
+    fn index(&self, tile_pos: &TilePos) -> usize {
+        (tile_pos.y * self.size.x + tile_pos.x) as usize
+    }
+
+    fn tile_pos(&self, index: usize) -> TilePos {
+        TilePos {
+            x: index as u32 % self.size.x,
+            y: index as u32 / self.size.x,
+        }
+    }
+
+    /// Indices of the up to 8 tiles around `tile_pos`.
+    fn neighbors(size: TilemapSize, tile_pos: TilePos) -> impl Iterator<Item = usize> {
+        let position = IVec2::new(tile_pos.x as i32, tile_pos.y as i32);
+        (-1..=1)
+            .flat_map(|y| (-1..=1).map(move |x| IVec2::new(x, y)))
+            .filter(|offset| *offset != IVec2::ZERO)
+            .map(move |offset| position + offset)
+            .filter(move |neighbor| {
+                neighbor.x >= 0
+                    && neighbor.y >= 0
+                    && neighbor.x < size.x as i32
+                    && neighbor.y < size.y as i32
+            })
+            .map(move |neighbor| (neighbor.y as u32 * size.x + neighbor.x as u32) as usize)
+    }
+
+    /// Trees without a pending action.
+    fn idle_trees(&self) -> Vec<(usize, BoardTree)> {
+        self.tiles
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| self.actions[*index].is_none())
+            .filter_map(|(index, tile)| tile.tree.map(|tree| (index, tree)))
+            .collect()
+    }
+
+    fn level_grid(&self, trees: &[(usize, BoardTree)]) -> LevelGrid {
+        LevelGrid::new(
+            self.size,
+            trees
+                .iter()
+                .map(|(index, tree)| (self.tile_pos(*index), tree.tree)),
+        )
+    }
+
+    fn schedule(&mut self, index: usize, kind: TreeActionKind, duration: Duration) -> u64 {
+        let id = self.next_action_id;
+        self.next_action_id += 1;
+
+        self.actions[index] = Some(BoardAction {
+            kind,
+            duration,
+            burns: 0,
+            id,
+        });
+
+        id
+    }
+
+    fn remove_tree(&mut self, index: usize) -> Option<BoardTree> {
+        self.actions[index] = None;
+        self.tiles[index].tree.take()
+    }
+
+    fn setup_growing(&mut self, rng: &mut impl RngCore) {
+        let trees = self.idle_trees();
+        let level_grid = self.level_grid(&trees);
+
+        for (index, tree) in trees {
+            let neighbor_level = level_grid.neighbor_level(&self.tile_pos(index));
+            if rules::can_grow(&tree.tree, neighbor_level) {
+                let growth_speed = self.tiles[index].ground.growth_speed();
+                let duration = self.timers.duration(rng).div_f32(growth_speed);
+                self.schedule(index, TreeActionKind::Growing, duration);
+            }
+        }
+    }
+
+    fn setup_overcrowd_dying(&mut self, rng: &mut impl RngCore) {
+        let trees = self.idle_trees();
+        let level_grid = self.level_grid(&trees);
+
+        for (index, _) in trees {
+            let damage = rules::crowding_damage(level_grid.neighbor_level(&self.tile_pos(index)));
+            if damage > 0 {
+                if let Some(tree) = &mut self.tiles[index].tree {
+                    if tree.health.damage(damage) {
+                        self.schedule(index, TreeActionKind::Dying, self.timers.duration(rng));
+                    }
+                }
+            }
+        }
+    }
+
+    fn setup_old_age_dying(&mut self, rng: &mut impl RngCore) {
+        for (index, tree) in self.idle_trees() {
+            if rules::dies_of_old_age(&tree.tree, &tree.age) {
+                self.schedule(index, TreeActionKind::Dying, self.timers.duration(rng));
+            }
+        }
+    }
+
+    fn setup_seedling_dying(&mut self, rng: &mut impl RngCore) {
+        for index in 0..self.tiles.len() {
+            if self.tiles[index]
+                .tree
+                .is_some_and(|tree| rules::dies_of_frost(&tree.tree))
+            {
+                self.schedule(index, TreeActionKind::Dying, self.timers.duration(rng));
+            }
+        }
+    }
+
+    fn setup_felling(&mut self, rng: &mut impl RngCore) {
+        for index in 0..self.tiles.len() {
+            if self.tiles[index]
+                .tree
+                .is_some_and(|tree| rules::is_felled(&tree.tree, tree.bad_weather))
+            {
+                self.schedule(index, TreeActionKind::Felling, self.timers.duration(rng));
+            }
+        }
+    }
+
+    fn setup_infestation(&mut self, rng: &mut impl RngCore) {
+        for (index, tree) in self.idle_trees() {
+            if tree.quarantined {
+                continue;
+            }
+
+            if tree.infested {
+                self.schedule(index, TreeActionKind::Infesting, self.timers.duration(rng));
+            } else if rules::catches_pests(&tree.health, rng) {
+                if let Some(tree) = &mut self.tiles[index].tree {
+                    tree.infested = true;
+                }
+            }
+        }
+    }
+
+    fn setup_seed_dispersal(&mut self, seeds: &mut Vec<IVec2>, rng: &mut impl RngCore) {
+        for (index, tile) in self.tiles.iter().enumerate() {
+            if let Some(tree) = tile.tree {
+                let tile_pos = self.tile_pos(index);
+                for _ in 0..tree.tree.seeds() {
+                    seeds.push(rules::seed_landing(&tile_pos, rng));
+                }
+            }
+        }
+    }
+
+    fn setup_sprouting(&mut self, sprouts: &mut Vec<TilePos>, rng: &mut impl RngCore) {
+        for index in 0..self.tiles.len() {
+            let tile = &mut self.tiles[index];
+            if tile.seeds > 0 && tile.tree.is_none() && rules::sprouts(&tile.ground, rng) {
+                tile.seeds -= 1;
+                sprouts.push(self.tile_pos(index));
+            }
+        }
+    }
+
+    /// Stores seeds in the soil, like the `DropSeed` event. Returns the tiles they sprout on.
+    pub fn drop_seeds(&mut self, seeds: impl IntoIterator<Item = IVec2>) -> Vec<TilePos> {
+        // Only one seed can sprout on a free tile, the rest are stored.
+        let mut sprouting = HashSet::new();
+
+        for position in seeds {
+            let tile_pos = TilePos {
+                x: position.x.clamp(0, self.size.x as i32 - 1) as u32,
+                y: position.y.clamp(0, self.size.y as i32 - 1) as u32,
+            };
+            let on_map = IVec2::new(tile_pos.x as i32, tile_pos.y as i32) == position;
+
+            let tile = self.tile_mut(&tile_pos);
+            if !(on_map && tile.tree.is_none() && sprouting.insert(tile_pos)) {
+                tile.seeds += 1;
+            }
+        }
+
+        sprouting.into_iter().collect()
+    }
+
+    /// Resolves actions in the order their timers would finish.
+    fn resolve_actions(&mut self, rng: &mut impl RngCore) {
+        let mut queue: BinaryHeap<_> = self
+            .actions
+            .iter()
+            .enumerate()
+            .filter_map(|(index, action)| {
+                action.map(|action| Reverse((action.duration, action.id, index)))
+            })
+            .collect();
+
+        while let Some(Reverse((now, id, index))) = queue.pop() {
+            let Some(action) = self.actions[index].filter(|action| action.id == id) else {
+                continue;
+            };
+
+            match action.kind {
+                TreeActionKind::Growing => {
+                    if let Some(tree) = &mut self.tiles[index].tree {
+                        if let Some(next_tree) = tree.tree.next() {
+                            tree.tree = next_tree;
+                        }
+                    }
+                    self.actions[index] = None;
+                }
+                TreeActionKind::Dying => {
+                    self.remove_tree(index);
+                }
+                TreeActionKind::Burning => {
+                    let burns = action.burns + 1;
+                    if rules::spreads_fire(burns) {
+                        let tile_pos = self.tile_pos(index);
+                        for neighbor in Self::neighbors(self.size, tile_pos) {
+                            let neighbor_action = self.actions[neighbor].map(|action| action.kind);
+                            if self.tiles[neighbor].tree.is_some()
+                                && rules::catches_fire(neighbor_action)
+                            {
+                                let duration = self.timers.duration(rng);
+                                let id = self.schedule(neighbor, TreeActionKind::Burning, duration);
+                                queue.push(Reverse((now + duration, id, neighbor)));
+                            }
+                        }
+
+                        // The timer repeats
+                        self.actions[index] = Some(BoardAction { burns, ..action });
+                        queue.push(Reverse((now + action.duration, id, index)));
+                    } else if let Some(tree) = self.remove_tree(index) {
+                        if let Some(ground) = rules::burned_ground(&tree.tree) {
+                            self.tiles[index].ground = ground;
+                        }
+                    }
+                }
+                TreeActionKind::Felling => {
+                    if let Some(tree) = self.remove_tree(index) {
+                        self.score +=
+                            rules::felling_score(&tree.tree, &mut self.tiles[index].ground);
+                    }
+                }
+                TreeActionKind::Infesting => {
+                    self.actions[index] = None;
+                    if self.infest(index, rng) {
+                        let duration = self.timers.duration(rng);
+                        let id = self.schedule(index, TreeActionKind::Dying, duration);
+                        queue.push(Reverse((now + duration, id, index)));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if the pests leave the tree without health, so it dies of them.
+    fn infest(&mut self, index: usize, rng: &mut impl RngCore) -> bool {
+        let Some(tree) = self.tiles[index].tree else {
+            return false;
+        };
+
+        // Pests spread to neighbors of the same stage
+        for neighbor in Self::neighbors(self.size, self.tile_pos(index)) {
+            if let Some(neighbor_tree) = &mut self.tiles[neighbor].tree {
+                if !neighbor_tree.infested
+                    && !neighbor_tree.quarantined
+                    && rules::spreads_pests(&tree.tree, &neighbor_tree.tree, rng)
+                {
+                    neighbor_tree.infested = true;
+                }
+            }
+        }
+
+        self.tiles[index]
+            .tree
+            .as_mut()
+            .is_some_and(|tree| tree.health.damage(rules::PEST_DAMAGE))
+    }
+}
+
+/// Reads the level's tiles into a [`Board`], to look ahead from the current state of the game.
+#[derive(SystemParam)]
+pub struct LevelBoard<'w, 's> {
+    tree_tile_storage_q: Query<'w, 's, &'static TileStorage, With<TreeLayer>>,
+    tree_q: Query<
+        'w,
+        's,
+        (
+            &'static TilePos,
+            &'static Tree,
+            &'static Age,
+            &'static Health,
+            Has<Infested>,
+            Has<Quarantined>,
+            Has<BadWeather>,
+        ),
+    >,
+    ground_q: Query<'w, 's, (&'static TilePos, &'static Ground, &'static SeedBank)>,
+    timers: Res<'w, ActionTimers>,
+}
+
+impl LevelBoard<'_, '_> {
+    /// The board of the level, or `None` if no level is spawned.
+    pub fn board(&self) -> Option<Board> {
+        let tile_storage = self.tree_tile_storage_q.get_single().ok()?;
+        let mut board = Board::new(tile_storage.size);
+        board.timers = *self.timers;
+
+        for (tile_pos, ground, seed_bank) in &self.ground_q {
+            let tile = board.tile_mut(tile_pos);
+            tile.ground = *ground;
+            tile.seeds = seed_bank.0;
+        }
+
+        for (tile_pos, tree, age, health, infested, quarantined, bad_weather) in &self.tree_q {
+            board.tile_mut(tile_pos).tree = Some(BoardTree {
+                tree: *tree,
+                age: *age,
+                health: *health,
+                infested,
+                quarantined,
+                bad_weather,
+            });
+        }
+
+        Some(board)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::time::TimeUpdateStrategy;
+    use bevy_prng::WyRand;
+    use rand_core::SeedableRng;
+
+    use crate::{
+        game::{
+            season::{
+                state::{NextSeasonState, SeasonState},
+                Season,
+            },
+            spawn::level::{CustomLevel, SelectedTile, MAP_SIZE},
+            GameYears, LevelSeed, Score,
+        },
+        HeadlessPlugin,
+    };
+
+    use super::*;
+
+    const SEED: u64 = 3;
+    /// Years played, a whole game.
+    const YEARS: u32 = 3;
+    /// Time passing each frame, like a game running at 60 fps.
+    const FRAME: Duration = Duration::from_nanos(16_666_667);
+    /// A season still going after this many frames is stuck.
+    const MAX_SEASON_FRAMES: u32 = 10_000;
+
+    /// User actions of each season, starting in spring.
+    const ACTIONS: [&[(u32, u32)]; 4] = [
+        &[(0, 0), (7, 7), (0, 7), (4, 3)],
+        &[(2, 3)],
+        &[(5, 6)],
+        &[(5, 4)],
+    ];
+
+    /// Stage and health of every tree, by position.
+    type Trees = Vec<(u32, u32, Tree, u32)>;
+
+    fn board_trees(board: &Board) -> Trees {
+        let mut trees: Trees = board
+            .trees()
+            .map(|(tile_pos, tree)| (tile_pos.x, tile_pos.y, tree.tree, tree.health.0))
+            .collect();
+        trees.sort_by_key(|(x, y, ..)| (*x, *y));
+        trees
+    }
+
+    fn level_trees(app: &mut App) -> Trees {
+        let mut trees: Trees = app
+            .world_mut()
+            .query::<(&TilePos, &Tree, &Health)>()
+            .iter(app.world())
+            .map(|(tile_pos, tree, health)| (tile_pos.x, tile_pos.y, *tree, health.0))
+            .collect();
+        trees.sort_by_key(|(x, y, ..)| (*x, *y));
+        trees
+    }
+
+    /// The game plays a level the same as the board, so looking ahead on a board
+    /// shows what the game will do.
+    #[test]
+    fn board_plays_like_the_game() {
+        let size = TilemapSize {
+            x: MAP_SIZE,
+            y: MAP_SIZE,
+        };
+        let mut board = Board::generate(size, &mut WyRand::seed_from_u64(SEED));
+        board.timers = ActionTimers::Fixed;
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, HeadlessPlugin));
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(FRAME));
+        app.insert_resource(CustomLevel(board.clone()));
+        app.insert_resource(ActionTimers::Fixed);
+        app.insert_resource(LevelSeed(SEED));
+        // One more year than played, so the level stays when the game would end
+        app.insert_resource(GameYears(YEARS + 1));
+        app.update();
+
+        // The game reseeds its entropy with the level seed
+        let mut rng = WyRand::from_seed(SEED.to_le_bytes());
+
+        for year in 0..YEARS {
+            for season_kind in SeasonKind::ALL {
+                for &(x, y) in ACTIONS[season_kind.index()] {
+                    let tile_pos = TilePos { x, y };
+                    board.user_action(season_kind, &tile_pos, &mut rng);
+
+                    let world = app.world_mut();
+                    world.resource_mut::<SelectedTile>().0 = Some(tile_pos);
+                    season_kind.user_action(&mut world.commands());
+                    world.flush();
+                }
+                // Planted trees spawn on the next update
+                app.update();
+
+                board.simulate(season_kind, &mut rng);
+                board.transition();
+
+                app.world_mut()
+                    .send_event(NextSeasonState(SeasonState::Simulation));
+                let mut frames = 0;
+                while app.world().resource::<Season>().kind == season_kind {
+                    app.update();
+                    frames += 1;
+                    assert!(frames < MAX_SEASON_FRAMES, "{season_kind:?} did not end");
+                }
+
+                assert_eq!(
+                    app.world().resource::<Score>().0,
+                    board.score,
+                    "score after {season_kind:?} of year {year}"
+                );
+                assert_eq!(
+                    level_trees(&mut app),
+                    board_trees(&board),
+                    "trees after {season_kind:?} of year {year}"
+                );
+            }
+        }
+    }
+}
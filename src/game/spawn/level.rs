@@ -1,78 +1,145 @@
 //! Spawn the main level by triggering other observers.
 
-use bevy::color::palettes::css::GREEN;
-use bevy::color::palettes::css::RED;
 use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
 use bevy_ecs_tilemap::TilemapPlugin;
+use itertools::Itertools;
 
 use bevy_ecs_tilemap::prelude::*;
 
 use crate::game::assets::ImageAssets;
-use crate::game::season::Season;
-use crate::screen::Screen;
-
-use super::tree::Tree;
+use crate::game::camera::PAN_MODIFIERS;
+use crate::game::gesture::Gesture;
+use crate::game::season::logic::{Infested, Quarantined};
+use crate::game::season::{BadWeather, Season};
+use crate::game::simulation::Board;
+use crate::screen::{Pause, Screen};
+use crate::ui::prelude::Accessibility;
+
+use super::picking::TilePicker;
 use super::tree::OVERLAY_TEXTURE_INDEX_TREE;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins(TilemapPlugin);
-    app.observe(spawn_level);
 
-    app.register_type::<(HighlightedTile, SelectedTile)>();
-    app.init_resource::<HighlightedTile>();
-    app.init_resource::<SelectedTile>();
-    app.add_systems(
-        Update,
-        (
-            reset_tile_color,
-            update_highlighted_tile_color,
-            update_selected_tile_color,
-        )
-            .chain()
-            .run_if(in_state(Screen::Playing)),
+    app.add_event::<TileCursorMoved>();
+    app.insert_gizmo_config(
+        TileOutlineGizmos,
+        GizmoConfig {
+            line_width: TILE_OUTLINE_WIDTH,
+            ..default()
+        },
     );
+    app.add_systems(Update, draw_tile_outlines.run_if(in_state(Screen::Playing)));
     app.add_systems(
         Update,
         (
             highlighted_tile_mouse,
+            highlighted_tile_keys,
             update_selected_tile_mouse,
             update_selected_tile_touch,
         )
             .chain()
-            .run_if(in_state(Screen::Playing)),
+            .run_if(in_state(Pause::Running)),
     );
+}
+
+/// The level's tiles and the tiles picked on it. Needs no renderer, the tilemaps are only drawn
+/// with the [`TilemapPlugin`] added by [`plugin`].
+pub(super) fn simulation_plugin(app: &mut App) {
+    app.observe(spawn_level);
+
+    app.register_type::<(Ground, SeedBank)>();
+    app.register_type::<(
+        MapSize,
+        HighlightedTile,
+        SelectedTile,
+        InspectedTile,
+        MarkedTiles,
+    )>();
+    app.init_resource::<MapSize>();
+    app.init_resource::<HighlightedTile>();
+    app.init_resource::<SelectedTile>();
+    app.init_resource::<InspectedTile>();
+    app.init_resource::<MarkedTiles>();
 
     app.add_systems(
         Update,
-        update_ground_index.run_if(in_state(Screen::Playing)),
+        (
+            update_tile_color.run_if(
+                resource_changed::<HighlightedTile>
+                    .or_else(resource_changed::<SelectedTile>)
+                    .or_else(resource_changed::<MarkedTiles>)
+                    .or_else(resource_changed::<Accessibility>),
+            ),
+            update_ground_index,
+        )
+            .run_if(in_state(Screen::Playing)),
     );
 }
 
-#[derive(Debug, Component, Reflect)]
+/// Number of seasons nutrient soil lasts before it is back to normal.
+pub const NUTRIENT_SEASONS: u32 = 4;
+
+#[derive(Clone, Copy, Debug, Component, Reflect)]
 pub enum Ground {
     Normal,
-    Nutrient,
+    /// Holds the number of seasons left until the soil is depleted.
+    Nutrient(u32),
 }
 
 impl Ground {
-    pub fn name(&self) -> &'static str {
+    /// Translation key of the name.
+    pub fn name_key(&self) -> &'static str {
         match self {
-            Ground::Normal => "Normal",
-            Ground::Nutrient => "Nutrient",
+            Ground::Normal => "normal-ground",
+            Ground::Nutrient(_) => "nutrient-ground",
         }
     }
 
     pub fn texture_index_offset(&self) -> u32 {
         match self {
             Ground::Normal => 0,
-            Ground::Nutrient => 4,
+            Ground::Nutrient(_) => 4,
+        }
+    }
+
+    /// Trees on nutrient soil grow faster.
+    pub fn growth_speed(&self) -> f32 {
+        match self {
+            Ground::Normal => 1.0,
+            Ground::Nutrient(_) => 2.0,
+        }
+    }
+
+    /// Percent chance for a stored seed to sprout in spring.
+    pub fn sprout_chance(&self) -> u32 {
+        match self {
+            Ground::Normal => 25,
+            Ground::Nutrient(_) => 75,
+        }
+    }
+
+    /// The ground after one more season has passed.
+    pub fn decayed(&self) -> Self {
+        match self {
+            Ground::Nutrient(seasons) if *seasons > 1 => Ground::Nutrient(seasons - 1),
+            _ => Ground::Normal,
         }
     }
 }
 
+/// Seeds stored in the soil of a tile, waiting for space to sprout in spring.
+#[derive(Debug, Default, Component, Reflect)]
+pub struct SeedBank(pub u32);
+
 #[derive(Event, Debug)]
 pub struct SpawnLevel;
 
+/// A handcrafted board that is spawned instead of the starting forest, like a puzzle.
+#[derive(Debug, Resource)]
+pub struct CustomLevel(pub Board);
+
 #[derive(Debug, Default, Component, Reflect)]
 pub struct GroundLayer;
 
@@ -82,20 +149,69 @@ pub struct TreeLayer;
 #[derive(Debug, Default, Component, Reflect)]
 pub struct EffectLayer;
 
-const MAP_SIZE: u32 = 8;
+/// Width and height of the map in tiles. Picked in the settings, applies from the next game on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct MapSize(pub u32);
+
+impl Default for MapSize {
+    fn default() -> Self {
+        Self(MAP_SIZE)
+    }
+}
+
+impl MapSize {
+    /// Sizes to pick from. Larger maps are the 8x8 starting forest repeated.
+    pub const ALL: [u32; 5] = [MAP_SIZE, 16, 64, 128, LARGE_MAP_SIZE];
+
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|size| *size == self.0);
+        Self(Self::ALL[index.map_or(0, |index| (index + 1) % Self::ALL.len())])
+    }
+}
+
+pub const MAP_SIZE: u32 = 8;
+/// The largest map. The `simulation` benchmark keeps it playable.
+pub const LARGE_MAP_SIZE: u32 = 256;
+
+/// Trees in each 8x8 block of the map at the start of the game.
+const STARTING_TREES: [TilePos; 4] = [
+    TilePos { x: 2, y: 3 },
+    TilePos { x: 5, y: 4 },
+    TilePos { x: 3, y: 3 },
+    TilePos { x: 5, y: 6 },
+];
+
+/// Tiles with a tree at the start of the game, on a map of `map_size`.
+pub fn starting_trees(map_size: TilemapSize) -> impl Iterator<Item = TilePos> {
+    (0..map_size.x)
+        .step_by(MAP_SIZE as usize)
+        .cartesian_product((0..map_size.y).step_by(MAP_SIZE as usize))
+        .flat_map(|(x, y)| {
+            STARTING_TREES.map(|tile_pos| TilePos::new(x + tile_pos.x, y + tile_pos.y))
+        })
+        .filter(move |tile_pos| tile_pos.within_map_bounds(&map_size))
+}
 
 fn spawn_level(
     _trigger: Trigger<SpawnLevel>,
     mut commands: Commands,
     image_assets: Res<ImageAssets>,
+    size: Res<MapSize>,
+    custom_level: Option<Res<CustomLevel>>,
 ) {
+    let board = match custom_level {
+        Some(custom_level) => custom_level.0.clone(),
+        None => Board::level(TilemapSize {
+            x: size.0,
+            y: size.0,
+        }),
+    };
+
     // GroundLayer
     let texture_handle = image_assets.ground_tileset.clone_weak();
 
-    let map_size = TilemapSize {
-        x: MAP_SIZE,
-        y: MAP_SIZE,
-    };
+    let map_size = board.size();
     let mut tile_storage = TileStorage::empty(map_size);
     let tilemap_entity = commands.spawn_empty().id();
     let tilemap_id = TilemapId(tilemap_entity);
@@ -104,6 +220,7 @@ fn spawn_level(
         for x in 0..map_size.x {
             for y in 0..map_size.y {
                 let tile_pos = TilePos { x, y };
+                let tile = board.tile(&tile_pos);
 
                 let tile_entity = parent
                     .spawn((
@@ -113,7 +230,8 @@ fn spawn_level(
                             texture_index: TileTextureIndex(0),
                             ..Default::default()
                         },
-                        Ground::Normal,
+                        tile.ground,
+                        SeedBank(tile.seeds),
                     ))
                     .id();
                 tile_storage.set(&tile_pos, tile_entity);
@@ -135,7 +253,7 @@ fn spawn_level(
             tile_size,
             map_type,
             render_settings: TilemapRenderSettings {
-                render_chunk_size: UVec2::new(MAP_SIZE, 1),
+                render_chunk_size: UVec2::new(map_size.x, 1),
                 y_sort: true,
             },
             transform: get_tilemap_center_transform(&map_size, &grid_size, &map_type, 0.0),
@@ -153,24 +271,28 @@ fn spawn_level(
     let tilemap_id = TilemapId(tilemap_entity);
 
     commands.entity(tilemap_id.0).with_children(|parent| {
-        for tile_pos in [
-            TilePos::new(2, 3),
-            TilePos::new(5, 4),
-            TilePos::new(3, 3),
-            TilePos::new(5, 6),
-        ] {
-            let tile_entity = parent
-                .spawn((
-                    TileBundle {
-                        position: tile_pos,
-                        tilemap_id,
-                        texture_index: TileTextureIndex(OVERLAY_TEXTURE_INDEX_TREE),
-                        ..Default::default()
-                    },
-                    Tree::Immature,
-                ))
-                .id();
-            tile_storage.set(&tile_pos, tile_entity);
+        for (tile_pos, tree) in board.trees() {
+            let mut tile_entity = parent.spawn((
+                TileBundle {
+                    position: tile_pos,
+                    tilemap_id,
+                    texture_index: TileTextureIndex(OVERLAY_TEXTURE_INDEX_TREE),
+                    ..Default::default()
+                },
+                tree.tree,
+                tree.age,
+                tree.health,
+            ));
+            if tree.infested {
+                tile_entity.insert(Infested);
+            }
+            if tree.quarantined {
+                tile_entity.insert(Quarantined);
+            }
+            if tree.bad_weather {
+                tile_entity.insert(BadWeather);
+            }
+            tile_storage.set(&tile_pos, tile_entity.id());
         }
     });
 
@@ -188,7 +310,7 @@ fn spawn_level(
             tile_size,
             map_type,
             render_settings: TilemapRenderSettings {
-                render_chunk_size: UVec2::new(MAP_SIZE, 1),
+                render_chunk_size: UVec2::new(map_size.x, 1),
                 y_sort: true,
             },
             transform: get_tilemap_center_transform(&map_size, &grid_size, &map_type, 1.0),
@@ -218,7 +340,7 @@ fn spawn_level(
             tile_size,
             map_type,
             render_settings: TilemapRenderSettings {
-                render_chunk_size: UVec2::new(MAP_SIZE, 1),
+                render_chunk_size: UVec2::new(map_size.x, 1),
                 y_sort: true,
             },
             transform: get_tilemap_center_transform(&map_size, &grid_size, &map_type, 2.0),
@@ -229,122 +351,258 @@ fn spawn_level(
     ));
 }
 
-const HIGHLIGHT_COLOR: Color = bevy::prelude::Color::Srgba(RED);
-const SELECTED_COLOR: Color = bevy::prelude::Color::Srgba(GREEN);
-
-fn reset_tile_color(mut tile_colors: Query<&mut TileColor>) {
+/// Colors the marked, highlighted and selected tiles, and resets the previously colored ones.
+/// Only runs when any of them changes, instead of touching every tile each frame.
+fn update_tile_color(
+    highlighted_tile: Res<HighlightedTile>,
+    selected_tile: Res<SelectedTile>,
+    marked_tiles: Res<MarkedTiles>,
+    accessibility: Res<Accessibility>,
+    ground_tile_storages: Query<&TileStorage, With<GroundLayer>>,
+    mut tile_colors: Query<&mut TileColor>,
+    mut colored_tiles: Local<Vec<Entity>>,
+) {
     /* Reset color */
-    for mut tile_colors in &mut tile_colors {
-        *tile_colors = TileColor::default();
+    for entity in colored_tiles.drain(..) {
+        if let Ok(mut tile_color) = tile_colors.get_mut(entity) {
+            *tile_color = TileColor::default();
+        }
+    }
+
+    let tile_storage = ground_tile_storages.single();
+    let colors = accessibility.highlight_colors;
+    // Later colors win, so the cursor stays visible on marked tiles
+    let marked = marked_tiles
+        .tiles
+        .iter()
+        .map(|tile_pos| (Some(*tile_pos), colors.marked()));
+    let cursors = [
+        (highlighted_tile.0, colors.highlighted()),
+        (selected_tile.0, colors.selected()),
+    ];
+    for (tile_pos, color) in marked.chain(cursors) {
+        if let Some(entity) = tile_pos.and_then(|tile_pos| tile_storage.get(&tile_pos)) {
+            if let Ok(mut tile_color) = tile_colors.get_mut(entity) {
+                *tile_color = TileColor(color);
+                colored_tiles.push(entity);
+            }
+        }
     }
 }
 
-fn window_pos_to_tile_pos(
-    cam_tuple: (&GlobalTransform, &Camera),
-    window_pos: Vec2,
-    tilemap_tuple: (&TilemapSize, &TilemapGridSize, &TilemapType, &Transform),
-) -> Option<TilePos> {
-    let (cam_t, cam) = cam_tuple;
-
-    if let Some(cursor_world_position) = cam.viewport_to_world_2d(cam_t, window_pos) {
-        let (map_size, grid_size, map_type, map_transform) = tilemap_tuple;
-
-        // We need to make sure that the cursor's world position is correct relative to the map
-        // due to any map transformation.
-        let cursor_in_map_pos = {
-            // Extend the cursor_pos vec2 by 0.0 and 1.0
-            let cursor_world_position = Vec4::from((cursor_world_position, 0.0, 1.0));
-            let cursor_in_map_pos =
-                map_transform.compute_matrix().inverse() * cursor_world_position;
-            cursor_in_map_pos.xy()
-        };
-        // TODO: Not sure why this is necessary
-        let cursor_in_map_pos = cursor_in_map_pos + Vec2::new(0.0, grid_size.y / 2.0);
+#[derive(Default, Reflect, GizmoConfigGroup)]
+struct TileOutlineGizmos;
 
-        // Once we have a world position we can transform it into a possible tile position.
-        TilePos::from_world_pos(&cursor_in_map_pos, map_size, grid_size, map_type)
-    } else {
-        None
+const TILE_OUTLINE_WIDTH: f32 = 4.0;
+/// The top face of a ground sprite sits this far below the tile center,
+/// as the 112px tall sprites are centered on their 32px tall grid cell.
+const GROUND_FACE_OFFSET: Vec2 = Vec2::new(0.0, -24.0);
+
+/// Outlines the top face of the marked tiles, and of the highlighted and selected tiles
+/// if enabled in the accessibility settings, so they stand out without telling colors apart.
+fn draw_tile_outlines(
+    mut gizmos: Gizmos<TileOutlineGizmos>,
+    highlighted_tile: Res<HighlightedTile>,
+    selected_tile: Res<SelectedTile>,
+    marked_tiles: Res<MarkedTiles>,
+    accessibility: Res<Accessibility>,
+    tilemap_q: Query<(&TilemapGridSize, &TilemapType, &GlobalTransform), With<GroundLayer>>,
+) {
+    let Ok((grid_size, map_type, map_transform)) = tilemap_q.get_single() else {
+        return;
+    };
+
+    let colors = accessibility.highlight_colors;
+    let marked = marked_tiles
+        .tiles
+        .iter()
+        .map(|tile_pos| (Some(*tile_pos), colors.marked()));
+    let cursors = [
+        (highlighted_tile.0, colors.highlighted()),
+        (selected_tile.0, colors.selected()),
+    ]
+    .into_iter()
+    .filter(|_| accessibility.tile_outlines);
+    for (tile_pos, color) in marked.chain(cursors) {
+        if let Some(tile_pos) = tile_pos {
+            let center = map_transform
+                .transform_point(tile_pos.center_in_world(grid_size, map_type).extend(0.0))
+                .xy()
+                + GROUND_FACE_OFFSET;
+            let half_width = Vec2::new(grid_size.x / 2.0, 0.0);
+            let half_height = Vec2::new(0.0, grid_size.y / 2.0);
+            gizmos.linestrip_2d(
+                [
+                    center - half_width,
+                    center + half_height,
+                    center + half_width,
+                    center - half_height,
+                    center - half_width,
+                ],
+                color,
+            );
+        }
     }
 }
 
-#[derive(Debug, Default, Resource, Reflect)]
+#[derive(Debug, Default, PartialEq, Eq, Resource, Reflect)]
 #[reflect(Resource)]
 pub struct HighlightedTile(pub Option<TilePos>);
 
+/// Tiles pointed out to the player, for example by the tutorial.
+/// If `exclusive`, no other tile can be selected.
+#[derive(Debug, Default, PartialEq, Eq, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct MarkedTiles {
+    pub tiles: Vec<TilePos>,
+    pub exclusive: bool,
+}
+
+impl MarkedTiles {
+    /// Whether `tile_pos` can be selected.
+    pub fn allows(&self, tile_pos: &TilePos) -> bool {
+        !self.exclusive || self.tiles.contains(tile_pos)
+    }
+}
+
+/// Follows the mouse cursor, also while the camera moves underneath it.
+/// Stops following camera moves once the tile cursor is moved with keys.
 fn highlighted_tile_mouse(
-    camera_q: Query<(&GlobalTransform, &Camera)>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    camera_q: Query<Ref<GlobalTransform>, With<Camera>>,
     mut cursor_moved_events: EventReader<CursorMoved>,
+    mut tile_cursor_events: EventReader<TileCursorMoved>,
     mut highlighted_tile: ResMut<HighlightedTile>,
-    tilemap_q: Query<(&TilemapSize, &TilemapGridSize, &TilemapType, &Transform), With<GroundLayer>>,
+    tile_picker: TilePicker,
+    mut follow_camera: Local<bool>,
 ) {
-    for cursor_moved in cursor_moved_events.read() {
-        for cam_tuple in camera_q.iter() {
-            highlighted_tile.0 =
-                window_pos_to_tile_pos(cam_tuple, cursor_moved.position, tilemap_q.single());
+    if tile_cursor_events.read().count() > 0 {
+        *follow_camera = false;
+    }
+    let cursor_moved = cursor_moved_events.read().count() > 0;
+    if cursor_moved {
+        *follow_camera = true;
+    }
+
+    if let Some(cursor_position) = window_q.single().cursor_position() {
+        let camera_moved = camera_q.iter().any(|cam_t| cam_t.is_changed());
+        if cursor_moved || (*follow_camera && camera_moved) {
+            highlighted_tile.set_if_neq(HighlightedTile(tile_picker.pick(cursor_position)));
         }
     }
 }
 
-fn update_highlighted_tile_color(
-    highlighted_tile: Res<HighlightedTile>,
-    ground_tile_storages: Query<&TileStorage, With<GroundLayer>>,
-    mut tile_colors: Query<&mut TileColor>,
+/// A tile held with a long-press. Its info is shown instead of the selected tile's.
+#[derive(Debug, Default, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct InspectedTile(pub Option<TilePos>);
+
+/// Sent when the highlighted tile is moved with the keyboard or a gamepad.
+#[derive(Debug, Event)]
+pub struct TileCursorMoved(pub TilePos);
+
+/// Steps on the isometric grid. Each is turned 45° clockwise from the screen direction of its keys,
+/// so every key moves to a neighboring tile.
+const CURSOR_STEPS: [([KeyCode; 2], GamepadButtonType, IVec2); 4] = [
+    (
+        [KeyCode::ArrowUp, KeyCode::KeyW],
+        GamepadButtonType::DPadUp,
+        IVec2::Y,
+    ),
+    (
+        [KeyCode::ArrowRight, KeyCode::KeyD],
+        GamepadButtonType::DPadRight,
+        IVec2::X,
+    ),
+    (
+        [KeyCode::ArrowDown, KeyCode::KeyS],
+        GamepadButtonType::DPadDown,
+        IVec2::NEG_Y,
+    ),
+    (
+        [KeyCode::ArrowLeft, KeyCode::KeyA],
+        GamepadButtonType::DPadLeft,
+        IVec2::NEG_X,
+    ),
+];
+
+/// Moves the highlighted tile with the arrow keys, WASD or the gamepad D-pad.
+fn highlighted_tile_keys(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<ButtonInput<GamepadButton>>,
+    mut highlighted_tile: ResMut<HighlightedTile>,
+    mut tile_cursor_events: EventWriter<TileCursorMoved>,
+    tilemap_q: Query<&TilemapSize, With<GroundLayer>>,
 ) {
-    if let Some(tile_pos) = highlighted_tile.0 {
-        if let Some(entity) = ground_tile_storages.single().get(&tile_pos) {
-            if let Ok(mut tile_colors) = tile_colors.get_mut(entity) {
-                *tile_colors = TileColor(HIGHLIGHT_COLOR);
-            }
-        }
+    // These keys pan the camera instead
+    if keyboard_input.any_pressed(PAN_MODIFIERS) {
+        return;
+    }
+
+    let step: IVec2 = CURSOR_STEPS
+        .iter()
+        .filter(|(keys, button_type, _)| {
+            keyboard_input.any_just_pressed(*keys)
+                || gamepads.iter().any(|gamepad| {
+                    gamepad_input.just_pressed(GamepadButton::new(gamepad, *button_type))
+                })
+        })
+        .map(|(_, _, step)| *step)
+        .sum();
+
+    if step != IVec2::ZERO {
+        let map_size = tilemap_q.single();
+        let tile_pos = match highlighted_tile.0 {
+            Some(tile_pos) => TilePos {
+                x: (tile_pos.x as i32 + step.x).clamp(0, map_size.x as i32 - 1) as u32,
+                y: (tile_pos.y as i32 + step.y).clamp(0, map_size.y as i32 - 1) as u32,
+            },
+            None => TilePos::new(map_size.x / 2, map_size.y / 2),
+        };
+
+        highlighted_tile.0 = Some(tile_pos);
+        tile_cursor_events.send(TileCursorMoved(tile_pos));
     }
 }
 
-#[derive(Debug, Default, Resource, Reflect)]
+#[derive(Debug, Default, PartialEq, Eq, Resource, Reflect)]
 #[reflect(Resource)]
 pub struct SelectedTile(pub Option<TilePos>);
 
 fn update_selected_tile_mouse(
     highlighted_tile: Res<HighlightedTile>,
+    marked_tiles: Res<MarkedTiles>,
     mouse_input: Res<ButtonInput<MouseButton>>,
     mut selected_tile: ResMut<SelectedTile>,
 ) {
-    if mouse_input.just_pressed(MouseButton::Left) && highlighted_tile.0.is_some() {
-        selected_tile.0 = highlighted_tile.0;
+    if let Some(tile_pos) = highlighted_tile.0 {
+        if mouse_input.just_pressed(MouseButton::Left) && marked_tiles.allows(&tile_pos) {
+            selected_tile.0 = Some(tile_pos);
+        }
     }
 }
 
+/// A tap selects a tile, a long-press shows its info until the finger is lifted.
+/// Drags and pinches move the camera instead.
 fn update_selected_tile_touch(
     mut selected_tile: ResMut<SelectedTile>,
-    mut touch_events: EventReader<TouchInput>,
-    camera_q: Query<(&GlobalTransform, &Camera)>,
-    tilemap_q: Query<(&TilemapSize, &TilemapGridSize, &TilemapType, &Transform), With<GroundLayer>>,
-) {
-    for touch_event in touch_events.read() {
-        match touch_event.phase {
-            bevy::input::touch::TouchPhase::Started
-            | bevy::input::touch::TouchPhase::Moved
-            | bevy::input::touch::TouchPhase::Ended => {
-                for cam_tuple in camera_q.iter() {
-                    selected_tile.0 =
-                        window_pos_to_tile_pos(cam_tuple, touch_event.position, tilemap_q.single());
-                }
-            }
-            bevy::input::touch::TouchPhase::Canceled => continue,
-        }
-    }
-}
-
-fn update_selected_tile_color(
-    selected_tile: Res<SelectedTile>,
-    ground_tile_storages: Query<&TileStorage, With<GroundLayer>>,
-    mut tile_colors: Query<&mut TileColor>,
+    mut inspected_tile: ResMut<InspectedTile>,
+    marked_tiles: Res<MarkedTiles>,
+    mut gesture_events: EventReader<Gesture>,
+    tile_picker: TilePicker,
 ) {
-    if let Some(tile_pos) = selected_tile.0 {
-        if let Some(entity) = ground_tile_storages.single().get(&tile_pos) {
-            if let Ok(mut tile_colors) = tile_colors.get_mut(entity) {
-                *tile_colors = TileColor(SELECTED_COLOR);
+    for gesture in gesture_events.read() {
+        match *gesture {
+            Gesture::Tap(position) => {
+                let tile_pos = tile_picker
+                    .pick(position)
+                    .filter(|tile_pos| marked_tiles.allows(tile_pos));
+                selected_tile.set_if_neq(SelectedTile(tile_pos));
             }
+            Gesture::LongPress(position) => inspected_tile.0 = tile_picker.pick(position),
+            Gesture::LongPressEnded => inspected_tile.0 = None,
+            Gesture::Drag(_) | Gesture::Pinch { .. } => {}
         }
     }
 }
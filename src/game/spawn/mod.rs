@@ -5,8 +5,14 @@
 use bevy::prelude::*;
 
 pub mod level;
+pub mod picking;
 pub mod tree;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((level::plugin, tree::plugin));
+    app.add_plugins(level::plugin);
+}
+
+/// Spawning the level and its trees, without rendering or input.
+pub(super) fn simulation_plugin(app: &mut App) {
+    app.add_plugins((level::simulation_plugin, tree::plugin));
 }
@@ -0,0 +1,146 @@
+//! Picks the tile the player sees under a window position.
+//! Tree sprites are much taller than the ground diamond, so their canopies cover the tiles behind them.
+//! Sprites are tested front to back, and only where they are not transparent.
+
+use bevy::{ecs::system::SystemParam, prelude::*, render::render_resource::TextureFormat};
+use bevy_ecs_tilemap::prelude::*;
+use itertools::Itertools;
+
+use super::level::{GroundLayer, TreeLayer};
+
+/// Sprite pixels with at least this alpha can be picked.
+const PICK_ALPHA_THRESHOLD: u8 = 128;
+
+#[derive(SystemParam)]
+pub struct TilePicker<'w, 's> {
+    camera_q: Query<'w, 's, (&'static GlobalTransform, &'static Camera)>,
+    layer_q: Query<
+        'w,
+        's,
+        (
+            &'static TileStorage,
+            &'static TilemapSize,
+            &'static TilemapGridSize,
+            &'static TilemapTileSize,
+            &'static TilemapType,
+            &'static TilemapTexture,
+            &'static GlobalTransform,
+        ),
+        Or<(With<TreeLayer>, With<GroundLayer>)>,
+    >,
+    texture_index_q: Query<'w, 's, &'static TileTextureIndex>,
+    images: Res<'w, Assets<Image>>,
+}
+
+impl TilePicker<'_, '_> {
+    /// The tile whose tree or ground sprite is drawn on top at `window_pos`.
+    pub fn pick(&self, window_pos: Vec2) -> Option<TilePos> {
+        let (cam_t, cam) = self.camera_q.get_single().ok()?;
+        let world_pos = cam.viewport_to_world_2d(cam_t, window_pos)?;
+
+        // Higher layers are drawn on top of lower ones
+        self.layer_q
+            .iter()
+            .sorted_by(|(.., a), (.., b)| b.translation().z.total_cmp(&a.translation().z))
+            .find_map(
+                |(
+                    tile_storage,
+                    map_size,
+                    grid_size,
+                    tile_size,
+                    map_type,
+                    texture,
+                    map_transform,
+                )| {
+                    let map_pos = map_transform
+                        .affine()
+                        .inverse()
+                        .transform_point3(world_pos.extend(0.0))
+                        .xy();
+                    let image = texture
+                        .image_handles()
+                        .first()
+                        .and_then(|handle| self.images.get(*handle));
+
+                    covering_tiles(map_pos, map_size, grid_size, tile_size, map_type)
+                        .into_iter()
+                        .find(|tile_pos| {
+                            tile_storage
+                                .get(tile_pos)
+                                .and_then(|entity| self.texture_index_q.get(entity).ok())
+                                .is_some_and(|texture_index| {
+                                    let center = tile_pos.center_in_world(grid_size, map_type);
+                                    sprite_alpha(image, tile_size, texture_index, map_pos - center)
+                                        >= PICK_ALPHA_THRESHOLD
+                                })
+                        })
+                },
+            )
+    }
+}
+
+/// Tiles whose sprite rectangle contains `map_pos`, front to back.
+/// Expects the diamond isometric grid all layers use.
+fn covering_tiles(
+    map_pos: Vec2,
+    map_size: &TilemapSize,
+    grid_size: &TilemapGridSize,
+    tile_size: &TilemapTileSize,
+    map_type: &TilemapType,
+) -> Vec<TilePos> {
+    // Invert the diamond projection: screen x follows x + y, screen y follows y - x
+    let sum = map_pos.x / (grid_size.x / 2.0);
+    let difference = map_pos.y / (grid_size.y / 2.0);
+    let estimate = Vec2::new(sum - difference, sum + difference) / 2.0;
+    // Sprites reach this many tiles above and below their grid cell
+    let reach = (tile_size.y / grid_size.y).ceil() as i32;
+
+    let half_tile = Vec2::new(tile_size.x, tile_size.y) / 2.0;
+    let (x, y) = (estimate.x.round() as i32, estimate.y.round() as i32);
+
+    ((x - reach)..=(x + reach))
+        .cartesian_product((y - reach)..=(y + reach))
+        .filter(|&(x, y)| x >= 0 && y >= 0)
+        .map(|(x, y)| TilePos::new(x as u32, y as u32))
+        .filter(|tile_pos| tile_pos.within_map_bounds(map_size))
+        .map(|tile_pos| (tile_pos, tile_pos.center_in_world(grid_size, map_type)))
+        .filter(|(_, center)| (map_pos - *center).abs().cmplt(half_tile).all())
+        // Lower tiles are drawn in front
+        .sorted_by(|(_, a), (_, b)| a.y.total_cmp(&b.y))
+        .map(|(tile_pos, _)| tile_pos)
+        .collect()
+}
+
+/// Alpha of a tile sprite at `offset` from its center, looked up in the tileset atlas.
+/// Sprites are opaque if the atlas can not be read.
+fn sprite_alpha(
+    image: Option<&Image>,
+    tile_size: &TilemapTileSize,
+    texture_index: &TileTextureIndex,
+    offset: Vec2,
+) -> u8 {
+    let Some(image) = image else {
+        return u8::MAX;
+    };
+    if !matches!(
+        image.texture_descriptor.format,
+        TextureFormat::Rgba8UnormSrgb | TextureFormat::Rgba8Unorm
+    ) {
+        return u8::MAX;
+    }
+
+    let columns = (image.width() / tile_size.x as u32).max(1);
+    let sprite_origin = UVec2::new(
+        texture_index.0 % columns * tile_size.x as u32,
+        texture_index.0 / columns * tile_size.y as u32,
+    );
+    // Image rows go down, world y goes up
+    let pixel = Vec2::new(offset.x + tile_size.x / 2.0, tile_size.y / 2.0 - offset.y)
+        .floor()
+        .as_uvec2()
+        .min(UVec2::new(tile_size.x as u32, tile_size.y as u32) - 1);
+    let pixel = sprite_origin + pixel;
+
+    let index = (pixel.y * image.width() + pixel.x) as usize * 4 + 3;
+    image.data.get(index).copied().unwrap_or(u8::MAX)
+}
@@ -1,27 +1,28 @@
-use bevy::prelude::*;
-use bevy_ecs_tilemap::helpers::square_grid::neighbors::Neighbors;
+use bevy::{prelude::*, utils::HashSet};
 use bevy_ecs_tilemap::prelude::*;
 use bevy_ecs_tilemap::tiles::{TilePos, TileStorage};
 use bevy_prng::WyRand;
 use bevy_rand::prelude::GlobalEntropy;
 use itertools::Itertools;
 
-use crate::game::season::logic::TreeAction;
+use crate::game::audio::sfx::{GameplaySfx, PlaySfx};
+use crate::game::season::logic::{ActionTimers, TreeAction, TreeActionCause};
+use crate::game::season::rules::{can_grow, crowding_damage};
 use crate::game::season::Season;
 use crate::screen::Screen;
 
-use super::level::TreeLayer;
+use super::level::{Ground, GroundLayer, SeedBank, TreeLayer};
 
 pub(super) fn plugin(app: &mut App) {
-    app.register_type::<Tree>();
+    app.register_type::<(Tree, Age, Health)>();
     app.add_event::<SpawnTree>();
-    app.add_event::<DespawnTree>();
+    app.add_event::<DropSeed>();
     app.add_systems(
         Update,
         (
             //tree_game_of_life,
+            drop_seed.before(spawn_tree),
             spawn_tree,
-            despawn_tree,
         )
             .run_if(in_state(Screen::Playing)),
     );
@@ -63,12 +64,13 @@ impl Tree {
         }
     }
 
-    pub fn name(&self) -> &'static str {
+    /// Translation key of the name.
+    pub fn name_key(&self) -> &'static str {
         match self {
-            Tree::Seedling => "Seedling",
-            Tree::Immature => "Immature",
-            Tree::Mature => "Mature",
-            Tree::Overmature => "Overmature",
+            Tree::Seedling => "seedling",
+            Tree::Immature => "immature",
+            Tree::Mature => "mature",
+            Tree::Overmature => "overmature",
         }
     }
 
@@ -89,6 +91,49 @@ impl Tree {
             Tree::Overmature => 2,
         }
     }
+
+    /// Number of seeds the tree drops on its own in autumn.
+    pub fn seeds(&self) -> u32 {
+        match self {
+            Tree::Seedling => 0,
+            Tree::Immature => 0,
+            Tree::Mature => 1,
+            Tree::Overmature => 2,
+        }
+    }
+}
+
+/// Overmature trees die of old age once they have lived through this many seasons.
+pub const OLD_AGE: u32 = 8;
+
+/// Number of seasons a tree has lived through.
+#[derive(Clone, Copy, Default, Debug, Component, Reflect)]
+pub struct Age(pub u32);
+
+impl Age {
+    pub fn is_old(&self, tree: &Tree) -> bool {
+        matches!(tree, Tree::Overmature) && self.0 >= OLD_AGE
+    }
+}
+
+pub const MAX_HEALTH: u32 = 100;
+
+/// Health of a tree in percent. The tree dies once it drops to zero.
+#[derive(Clone, Copy, Debug, Component, Reflect)]
+pub struct Health(pub u32);
+
+impl Default for Health {
+    fn default() -> Self {
+        Self(MAX_HEALTH)
+    }
+}
+
+impl Health {
+    /// Lowers health by `amount`. Returns `true` if the tree has no health left.
+    pub fn damage(&mut self, amount: u32) -> bool {
+        self.0 = self.0.saturating_sub(amount);
+        self.0 == 0
+    }
 }
 
 #[derive(Debug, Event, PartialEq, Eq, Hash)]
@@ -124,6 +169,8 @@ fn spawn_tree(
                             ..Default::default()
                         },
                         event.tree,
+                        Age::default(),
+                        Health::default(),
                     ))
                     .id();
                 overlay_storage.set(&tile_pos, tile_entity);
@@ -132,64 +179,131 @@ fn spawn_tree(
                     season.user_action_resource -= 1;
                 }
             });
+
+            // Only trees planted by the player, sprouting seeds are silent
+            if event.use_resource {
+                commands.trigger(PlaySfx::GameplayAt(GameplaySfx::Plant, tile_pos));
+            }
         }
     }
 }
 
-fn despawn_tree(
-    mut commands: Commands,
-    mut despawn_tree_events: EventReader<DespawnTree>,
-    mut overlay_map: Query<&mut TileStorage, With<TreeLayer>>,
+/// Removes the tree on `tile_pos` from the map right away,
+/// so trees resolving after it in the same frame no longer see it.
+pub fn despawn_tree(
+    commands: &mut Commands,
+    tree_tile_storage: &mut TileStorage,
+    tile_pos: &TilePos,
 ) {
-    let mut overlay_storage = overlay_map.single_mut();
-    for event in despawn_tree_events.read().unique() {
-        let tile_pos = event.tile_pos;
+    if let Some(entity) = tree_tile_storage.checked_get(tile_pos) {
+        commands.entity(entity).despawn_recursive();
+        tree_tile_storage.remove(tile_pos);
+    }
+}
+
+/// A seed landing at `position`, which may be off the map.
+/// Seeds that can not sprout right away are stored in the [`SeedBank`] of the tile they land on,
+/// or of the closest tile at the edge of the map.
+#[derive(Debug, Event)]
+pub struct DropSeed {
+    pub position: IVec2,
+}
+
+fn drop_seed(
+    mut drop_seed_events: EventReader<DropSeed>,
+    mut spawn_tree_events: EventWriter<SpawnTree>,
+    tree_tile_storage_q: Query<&TileStorage, With<TreeLayer>>,
+    ground_tile_storage_q: Query<&TileStorage, With<GroundLayer>>,
+    mut seed_bank_q: Query<&mut SeedBank>,
+) {
+    let tree_tile_storage = tree_tile_storage_q.single();
+    let ground_tile_storage = ground_tile_storage_q.single();
+    let map_size = tree_tile_storage.size;
 
-        if let Some(entity) = overlay_storage.checked_get(&tile_pos) {
-            commands.entity(entity).despawn_recursive();
-            overlay_storage.remove(&tile_pos);
+    // Only one seed can sprout on a free tile, the rest are stored.
+    let mut sprouting = HashSet::new();
+
+    for event in drop_seed_events.read() {
+        let tile_pos = TilePos {
+            x: event.position.x.clamp(0, map_size.x as i32 - 1) as u32,
+            y: event.position.y.clamp(0, map_size.y as i32 - 1) as u32,
+        };
+        let on_map = IVec2::new(tile_pos.x as i32, tile_pos.y as i32) == event.position;
+
+        if on_map && tree_tile_storage.get(&tile_pos).is_none() && sprouting.insert(tile_pos) {
+            spawn_tree_events.send(SpawnTree {
+                tile_pos,
+                tree: Tree::Seedling,
+                use_resource: false,
+            });
+        } else if let Some(entity) = ground_tile_storage.get(&tile_pos) {
+            if let Ok(mut seed_bank) = seed_bank_q.get_mut(entity) {
+                seed_bank.0 += 1;
+            }
         }
     }
 }
 
-#[derive(Debug, Event, PartialEq, Eq, Hash)]
-pub struct DespawnTree {
-    pub tile_pos: TilePos,
+/// Levels of all trees on the map, so neighbor levels can be looked up
+/// without a query per neighbor. Keeps large maps fast.
+pub struct LevelGrid {
+    size: TilemapSize,
+    levels: Vec<u32>,
+}
+
+impl LevelGrid {
+    pub fn new(size: TilemapSize, trees: impl IntoIterator<Item = (TilePos, Tree)>) -> Self {
+        let mut levels = vec![0; size.count()];
+        for (tile_pos, tree) in trees {
+            levels[(tile_pos.y * size.x + tile_pos.x) as usize] = tree.level();
+        }
+
+        Self { size, levels }
+    }
+
+    /// Sum of the levels of the up to 8 trees around `tile_pos`.
+    pub fn neighbor_level(&self, tile_pos: &TilePos) -> u32 {
+        let mut neighbor_level = 0;
+        for y in tile_pos.y.saturating_sub(1)..=(tile_pos.y + 1).min(self.size.y - 1) {
+            for x in tile_pos.x.saturating_sub(1)..=(tile_pos.x + 1).min(self.size.x - 1) {
+                if x != tile_pos.x || y != tile_pos.y {
+                    neighbor_level += self.levels[(y * self.size.x + x) as usize];
+                }
+            }
+        }
+
+        neighbor_level
+    }
 }
 
 pub fn grow_logic(
     commands: &mut Commands,
     tree_tile_storage: &TileStorage,
     tree_q: Query<(Entity, &Tree, &TilePos), Without<TreeAction>>,
+    ground_tile_storage: &TileStorage,
+    ground_q: Query<&Ground>,
     rng: &mut GlobalEntropy<WyRand>,
+    timers: ActionTimers,
 ) {
+    let level_grid = LevelGrid::new(
+        tree_tile_storage.size,
+        tree_q.iter().map(|(_, tree, tile_pos)| (*tile_pos, *tree)),
+    );
+
     for (tree_entity, tree, tile_pos) in &tree_q {
-        if !matches!(tree, Tree::Overmature) {
-            let neighbor_level = Neighbors::get_square_neighboring_positions(
-                tile_pos,
-                &tree_tile_storage.size,
-                true,
-            )
-            .entities(tree_tile_storage)
-            .iter()
-            .map(|entity| {
-                if let Ok((_, tree, _)) = tree_q.get(*entity) {
-                    tree.level()
-                } else {
-                    0
-                }
-            })
-            .sum();
-
-            match neighbor_level {
-                0..=2 => {
-                    /* Grow */
-                    commands
-                        .entity(tree_entity)
-                        .insert(TreeAction::growing(rng));
-                }
-                _ => { /* Do nothing */ }
-            }
+        let neighbor_level = level_grid.neighbor_level(tile_pos);
+
+        if can_grow(tree, neighbor_level) {
+            /* Grow */
+            let growth_speed = ground_tile_storage
+                .get(tile_pos)
+                .and_then(|entity| ground_q.get(entity).ok())
+                .map_or(1.0, Ground::growth_speed);
+
+            commands.entity(tree_entity).insert(
+                TreeAction::growing(rng, timers, TreeActionCause::Space { neighbor_level })
+                    .with_speed(growth_speed),
+            );
         }
     }
 }
@@ -198,28 +312,30 @@ pub fn overcrowd_dying_logic(
     commands: &mut Commands,
     tree_tile_storage: &TileStorage,
     tree_q: Query<(Entity, &Tree, &TilePos), Without<TreeAction>>,
+    mut health_q: Query<&mut Health>,
     rng: &mut GlobalEntropy<WyRand>,
+    timers: ActionTimers,
 ) {
+    let level_grid = LevelGrid::new(
+        tree_tile_storage.size,
+        tree_q.iter().map(|(_, tree, tile_pos)| (*tile_pos, *tree)),
+    );
+
     for (tree_entity, _tree, tile_pos) in &tree_q {
-        let neighbor_level =
-            Neighbors::get_square_neighboring_positions(tile_pos, &tree_tile_storage.size, true)
-                .entities(tree_tile_storage)
-                .iter()
-                .map(|entity| {
-                    if let Ok((_, tree, _)) = tree_q.get(*entity) {
-                        tree.level()
-                    } else {
-                        0
-                    }
-                })
-                .sum();
-
-        match neighbor_level {
-            5.. => {
-                /* Dies */
-                commands.entity(tree_entity).insert(TreeAction::dying(rng));
+        let neighbor_level = level_grid.neighbor_level(tile_pos);
+        let damage = crowding_damage(neighbor_level);
+
+        /* Loses health, dies once there is none left */
+        if damage > 0 {
+            if let Ok(mut health) = health_q.get_mut(tree_entity) {
+                if health.damage(damage) {
+                    commands.entity(tree_entity).insert(TreeAction::dying(
+                        rng,
+                        timers,
+                        TreeActionCause::Overcrowding { neighbor_level },
+                    ));
+                }
             }
-            _ => { /* Do nothing */ }
         }
     }
 }
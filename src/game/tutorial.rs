@@ -0,0 +1,311 @@
+//! A guided first year. Each step marks tiles, explains the rule that is about to apply,
+//! and only lets the player continue with the expected action.
+
+use bevy::{prelude::*, ui::Val::*};
+use bevy_ecs_tilemap::tiles::TilePos;
+
+use crate::{
+    locale::LocalizedText,
+    screen::Screen,
+    ui::{
+        palette::{BUTTON_TEXT, NODE_BACKGROUND},
+        prelude::*,
+    },
+};
+
+use super::{
+    season::{state::SeasonState, Season, SeasonKind},
+    spawn::{
+        level::{MarkedTiles, SelectedTile},
+        tree::Tree,
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Tutorial>();
+
+    app.add_systems(
+        OnEnter(Screen::Playing),
+        (reset_tutorial, spawn_tutorial_ui).run_if(resource_exists::<Tutorial>),
+    );
+    app.add_systems(
+        Update,
+        (advance_tutorial, update_marked_tiles, update_tutorial_text)
+            .chain()
+            .run_if(in_state(Screen::Playing).and_then(resource_exists::<Tutorial>)),
+    );
+}
+
+/// Seeds the tutorial, so it plays out the same every time.
+pub const TUTORIAL_SEED: u64 = 2024;
+
+/// The tutorial is played while this exists. Inserted from the title screen.
+#[derive(Debug, Default, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct Tutorial {
+    /// Index into [`STEPS`], past the end once the tutorial is done.
+    step: usize,
+    /// Actions left in the season when the current step began.
+    actions_left: Option<usize>,
+}
+
+/// What the player has to do to finish a step.
+#[derive(Debug)]
+enum Expect {
+    /// Use the season's action on one of the marked tiles.
+    Action,
+    /// Start the simulation of the season.
+    Start,
+}
+
+/// The tiles a step marks.
+#[derive(Debug)]
+enum StepTiles {
+    None,
+    At(&'static [TilePos]),
+    /// All trees of these stages.
+    Trees(&'static [Tree]),
+}
+
+#[derive(Debug)]
+struct TutorialStep {
+    season: SeasonKind,
+    /// Translation key of the explanation.
+    text_key: &'static str,
+    tiles: StepTiles,
+    expect: Expect,
+}
+
+impl TutorialStep {
+    /// Steps begin once the player can act in their season.
+    fn is_active(&self, season: &Season) -> bool {
+        self.season == season.kind && matches!(season.state, SeasonState::UserInput)
+    }
+}
+
+/// The first year, starting from the fixed trees of the level.
+/// The tiles are picked to show the neighbor level rules on those trees.
+const STEPS: [TutorialStep; 13] = [
+    TutorialStep {
+        season: SeasonKind::Spring,
+        text_key: "tutorial-spring-grow",
+        tiles: StepTiles::At(&[TilePos { x: 4, y: 4 }]),
+        expect: Expect::Action,
+    },
+    TutorialStep {
+        season: SeasonKind::Spring,
+        text_key: "tutorial-spring-crowd",
+        tiles: StepTiles::At(&[TilePos { x: 2, y: 2 }]),
+        expect: Expect::Action,
+    },
+    TutorialStep {
+        season: SeasonKind::Spring,
+        text_key: "tutorial-spring-pair",
+        tiles: StepTiles::At(&[TilePos { x: 6, y: 5 }]),
+        expect: Expect::Action,
+    },
+    TutorialStep {
+        season: SeasonKind::Spring,
+        text_key: "tutorial-spring-alone",
+        tiles: StepTiles::At(&[TilePos { x: 1, y: 6 }]),
+        expect: Expect::Action,
+    },
+    TutorialStep {
+        season: SeasonKind::Spring,
+        text_key: "tutorial-spring-start",
+        tiles: StepTiles::At(&[TilePos { x: 3, y: 3 }]),
+        expect: Expect::Start,
+    },
+    TutorialStep {
+        season: SeasonKind::Summer,
+        text_key: "tutorial-summer-fire",
+        tiles: StepTiles::At(&[TilePos { x: 1, y: 6 }]),
+        expect: Expect::Action,
+    },
+    TutorialStep {
+        season: SeasonKind::Summer,
+        text_key: "tutorial-summer-start",
+        tiles: StepTiles::None,
+        expect: Expect::Start,
+    },
+    TutorialStep {
+        season: SeasonKind::Autumn,
+        text_key: "tutorial-autumn-gust",
+        tiles: StepTiles::Trees(&[Tree::Mature, Tree::Overmature]),
+        expect: Expect::Action,
+    },
+    TutorialStep {
+        season: SeasonKind::Autumn,
+        text_key: "tutorial-autumn-start",
+        tiles: StepTiles::None,
+        expect: Expect::Start,
+    },
+    TutorialStep {
+        season: SeasonKind::Winter,
+        text_key: "tutorial-winter-storm",
+        tiles: StepTiles::Trees(&[Tree::Mature, Tree::Overmature]),
+        expect: Expect::Action,
+    },
+    TutorialStep {
+        season: SeasonKind::Winter,
+        text_key: "tutorial-winter-storm-again",
+        tiles: StepTiles::Trees(&[Tree::Mature, Tree::Overmature]),
+        expect: Expect::Action,
+    },
+    TutorialStep {
+        season: SeasonKind::Winter,
+        text_key: "tutorial-winter-start",
+        tiles: StepTiles::Trees(&[Tree::Mature, Tree::Overmature]),
+        expect: Expect::Start,
+    },
+    // Shown in the next spring, until the player acts on their own
+    TutorialStep {
+        season: SeasonKind::Spring,
+        text_key: "tutorial-done",
+        tiles: StepTiles::None,
+        expect: Expect::Action,
+    },
+];
+
+fn reset_tutorial(mut tutorial: ResMut<Tutorial>) {
+    *tutorial = Tutorial::default();
+}
+
+/// Moves on once the season's `*UserAction` used up some of the actions left,
+/// or once the simulation started.
+fn advance_tutorial(mut tutorial: ResMut<Tutorial>, season: Res<Season>) {
+    let Some(step) = STEPS.get(tutorial.step) else {
+        return;
+    };
+
+    let active = step.is_active(&season);
+    let done = match step.expect {
+        Expect::Action => {
+            active
+                && tutorial
+                    .actions_left
+                    .is_some_and(|actions_left| season.user_action_resource < actions_left)
+        }
+        Expect::Start => {
+            step.season == season.kind && !matches!(season.state, SeasonState::UserInput)
+        }
+    };
+
+    if done {
+        tutorial.step += 1;
+        tutorial.actions_left = None;
+    } else if active && tutorial.actions_left.is_none() {
+        tutorial.actions_left = Some(season.user_action_resource);
+    }
+}
+
+/// Marks the tiles of the active step. While an action is expected, no other tile can be selected.
+fn update_marked_tiles(
+    tutorial: Res<Tutorial>,
+    season: Res<Season>,
+    mut marked_tiles: ResMut<MarkedTiles>,
+    mut selected_tile: ResMut<SelectedTile>,
+    tree_q: Query<(&Tree, &TilePos)>,
+) {
+    let marked = match STEPS
+        .get(tutorial.step)
+        .filter(|step| step.is_active(&season))
+    {
+        Some(step) => {
+            let mut tiles: Vec<TilePos> = match step.tiles {
+                StepTiles::None => Vec::new(),
+                StepTiles::At(tiles) => tiles.to_vec(),
+                StepTiles::Trees(stages) => tree_q
+                    .iter()
+                    .filter(|(tree, _)| stages.contains(tree))
+                    .map(|(_, tile_pos)| *tile_pos)
+                    .collect(),
+            };
+            tiles.sort_by_key(|tile_pos| (tile_pos.x, tile_pos.y));
+
+            // Without a tile to act on, the season plays like in the normal game
+            let exclusive = matches!(step.expect, Expect::Action) && !tiles.is_empty();
+            MarkedTiles { tiles, exclusive }
+        }
+        None => MarkedTiles::default(),
+    };
+
+    // A tile selected before the step began may not be allowed anymore
+    if selected_tile
+        .0
+        .is_some_and(|tile_pos| !marked.allows(&tile_pos))
+    {
+        selected_tile.0 = None;
+    }
+    marked_tiles.set_if_neq(marked);
+}
+
+#[derive(Debug, Component, Reflect)]
+struct TutorialUi;
+
+fn spawn_tutorial_ui(mut commands: Commands) {
+    commands
+        .spawn((
+            Name::new("Tutorial UI"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(56.0),
+                    position_type: PositionType::Absolute,
+                    top: Percent(2.0),
+                    left: Percent(22.0),
+                    padding: UiRect::all(Px(10.0)),
+                    ..default()
+                },
+                background_color: BackgroundColor(NODE_BACKGROUND),
+                ..default()
+            },
+            StateScoped(Screen::Playing),
+            TutorialUi,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 26.0,
+                        color: BUTTON_TEXT,
+                        ..default()
+                    },
+                ),
+                ScaledText::new(26.0),
+                LocalizedText::new(""),
+            ));
+        });
+}
+
+/// Explains the active step. Until the next step begins, the rule that is playing out stays explained.
+fn update_tutorial_text(
+    tutorial: Res<Tutorial>,
+    season: Res<Season>,
+    mut ui_q: Query<(&mut Visibility, &Children), With<TutorialUi>>,
+    mut text_q: Query<&mut LocalizedText>,
+) {
+    let text_key = match STEPS.get(tutorial.step) {
+        Some(step) if step.is_active(&season) => Some(step.text_key),
+        Some(_) => tutorial
+            .step
+            .checked_sub(1)
+            .map(|index| STEPS[index].text_key),
+        None => None,
+    };
+
+    for (mut visibility, children) in &mut ui_q {
+        visibility.set_if_neq(if text_key.is_some() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+
+        let mut texts = text_q.iter_many_mut(children);
+        while let Some(mut text) = texts.fetch_next() {
+            if let Some(text_key) = text_key.filter(|text_key| text.key != *text_key) {
+                text.key = text_key.into();
+            }
+        }
+    }
+}
@@ -1,19 +1,23 @@
-use bevy::color::palettes::css::{
-    BLACK, BLUE, BROWN, GREEN, RED, SLATE_GREY, WHITE, WHITE_SMOKE, YELLOW,
-};
+use bevy::color::palettes::css::{BLACK, BLUE, BROWN, RED, SLATE_GREY, WHITE, WHITE_SMOKE, YELLOW};
 use bevy::prelude::*;
 use bevy::ui::Val::*;
-use bevy_ecs_tilemap::tiles::TileStorage;
+use bevy_ecs_tilemap::tiles::{TilePos, TileStorage};
 
-use crate::screen::Screen;
+use crate::locale::{Locale, LocalizedText};
+use crate::screen::{Pause, Screen};
 use crate::ui::palette::{BUTTON_HOVERED_BACKGROUND, BUTTON_PRESSED_BACKGROUND, NODE_BACKGROUND};
-use crate::ui::prelude::{InteractionPalette, InteractionQuery};
+use crate::ui::prelude::{Accessibility, InteractionPalette, InteractionQuery, ScaledText};
 
 use super::assets::{ImageAssets, UiAssets};
+use super::hint::{FindHint, Hint};
+use super::season::logic::{Infested, Quarantined};
 use super::season::state::{NextSeasonState, SeasonState};
-use super::season::Season;
-use super::spawn::level::{Ground, GroundLayer, SelectedTile, TreeLayer};
-use super::spawn::tree::Tree;
+use super::season::{Season, SimulationSpeed};
+use super::spawn::level::{
+    Ground, GroundLayer, HighlightedTile, InspectedTile, MarkedTiles, SeedBank, SelectedTile,
+    TreeLayer,
+};
+use super::spawn::tree::{Age, Health, Tree};
 use super::Score;
 
 pub(super) fn plugin(app: &mut App) {
@@ -26,11 +30,22 @@ pub(super) fn plugin(app: &mut App) {
             update_selected_tree_text,
             update_selected_ground_image,
             update_selected_ground_text,
+            update_selected_tile_outlines,
             update_season_header,
             update_season_clock,
             update_season_description,
             update_season_action,
-            handle_season_action,
+            update_simulation_speed_buttons,
+            update_hint.run_if(resource_changed_or_removed::<Hint>()),
+            (
+                handle_season_action,
+                handle_season_action_keys,
+                handle_hint_action,
+                handle_hint_keys,
+                handle_simulation_speed_action,
+                handle_simulation_speed_keys,
+            )
+                .run_if(in_state(Pause::Running)),
         )
             .run_if(in_state(Screen::Playing)),
     );
@@ -126,7 +141,7 @@ fn selected_tile_tree_ui(parent: &mut ChildBuilder) {
                     ..default()
                 },
                 TextureAtlas::default(),
-                Outline::new(Val::Percent(2.0), Val::ZERO, GREEN.into()),
+                Outline::new(Val::Percent(2.0), Val::ZERO, Color::NONE),
                 SelectedTileTreeUi,
             ));
             parent.spawn((
@@ -139,16 +154,23 @@ fn selected_tile_tree_ui(parent: &mut ChildBuilder) {
                     },
                 )
                 .with_background_color(WHITE_SMOKE.into()),
-                Outline::new(Val::Percent(2.0), Val::ZERO, GREEN.into()),
+                Outline::new(Val::Percent(2.0), Val::ZERO, Color::NONE),
+                ScaledText::new(40.0),
                 SelectedTileTreeUi,
             ));
         });
 }
 
+/// The tile shown in the left panel: the one held with a long-press, or else the selected one.
+fn shown_tile(selected_tile: &SelectedTile, inspected_tile: &InspectedTile) -> Option<TilePos> {
+    inspected_tile.0.or(selected_tile.0)
+}
+
 fn update_selected_tree_image(
     mut selected_tree_images: Query<(&mut UiImage, &mut TextureAtlas), With<SelectedTileTreeUi>>,
     season: Res<Season>,
     selected_tile: Res<SelectedTile>,
+    inspected_tile: Res<InspectedTile>,
     tree_tile_storage: Query<&TileStorage, With<TreeLayer>>,
     trees: Query<&Tree>,
     image_assets: Res<ImageAssets>,
@@ -159,7 +181,7 @@ fn update_selected_tree_image(
         *atlas = TextureAtlas::default();
 
         // Do we have anything selected?
-        if let Some(tile_pos) = selected_tile.0 {
+        if let Some(tile_pos) = shown_tile(&selected_tile, &inspected_tile) {
             if let Some(entity) = tree_tile_storage.single().get(&tile_pos) {
                 if let Ok(tree) = trees.get(entity) {
                     *image = UiImage {
@@ -179,17 +201,29 @@ fn update_selected_tree_image(
 fn update_selected_tree_text(
     mut selected_tree_texts: Query<&mut Text, With<SelectedTileTreeUi>>,
     selected_tile: Res<SelectedTile>,
+    inspected_tile: Res<InspectedTile>,
     tree_tile_storage: Query<&TileStorage, With<TreeLayer>>,
-    trees: Query<&Tree>,
+    trees: Query<(&Tree, &Age, &Health, Has<Infested>, Has<Quarantined>)>,
+    locale: Locale,
 ) {
     for mut text in &mut selected_tree_texts {
-        text.sections[0].value = String::from("None");
+        text.sections[0].value = locale.text("none").into();
 
         // Do we have anything selected?
-        if let Some(tile_pos) = selected_tile.0 {
+        if let Some(tile_pos) = shown_tile(&selected_tile, &inspected_tile) {
             if let Some(entity) = tree_tile_storage.single().get(&tile_pos) {
-                if let Ok(tree) = trees.get(entity) {
-                    let text_value = tree.name().to_string();
+                if let Ok((tree, age, health, infested, quarantined)) = trees.get(entity) {
+                    let mut text_value = locale.format(
+                        "tree-info",
+                        &[&locale.text(tree.name_key()), &age.0, &health.0],
+                    );
+                    if infested {
+                        text_value.push('\n');
+                        text_value.push_str(locale.text("tree-infested"));
+                    } else if quarantined {
+                        text_value.push('\n');
+                        text_value.push_str(locale.text("tree-quarantined"));
+                    }
                     text.sections[0].value.clone_from(&text_value);
                 }
             }
@@ -230,7 +264,7 @@ fn selected_tile_ground_ui(parent: &mut ChildBuilder) {
                     ..default()
                 },
                 TextureAtlas::default(),
-                Outline::new(Val::Percent(2.0), Val::ZERO, GREEN.into()),
+                Outline::new(Val::Percent(2.0), Val::ZERO, Color::NONE),
                 SelectedTileGroundUi,
             ));
             parent.spawn((
@@ -243,7 +277,8 @@ fn selected_tile_ground_ui(parent: &mut ChildBuilder) {
                     },
                 )
                 .with_background_color(WHITE_SMOKE.into()),
-                Outline::new(Val::Percent(2.0), Val::ZERO, GREEN.into()),
+                Outline::new(Val::Percent(2.0), Val::ZERO, Color::NONE),
+                ScaledText::new(40.0),
                 SelectedTileGroundUi,
             ));
         });
@@ -256,6 +291,7 @@ fn update_selected_ground_image(
     >,
     season: Res<Season>,
     selected_tile: Res<SelectedTile>,
+    inspected_tile: Res<InspectedTile>,
     ground_tile_storage: Query<&TileStorage, With<GroundLayer>>,
     ground_q: Query<&Ground>,
     image_assets: Res<ImageAssets>,
@@ -266,7 +302,7 @@ fn update_selected_ground_image(
         *atlas = TextureAtlas::default();
 
         // Do we have anything selected?
-        if let Some(tile_pos) = selected_tile.0 {
+        if let Some(tile_pos) = shown_tile(&selected_tile, &inspected_tile) {
             if let Some(entity) = ground_tile_storage.single().get(&tile_pos) {
                 if let Ok(ground) = ground_q.get(entity) {
                     *image = UiImage {
@@ -287,17 +323,29 @@ fn update_selected_ground_image(
 fn update_selected_ground_text(
     mut selected_ground_texts: Query<&mut Text, With<SelectedTileGroundUi>>,
     selected_tile: Res<SelectedTile>,
+    inspected_tile: Res<InspectedTile>,
     ground_tile_storage: Query<&TileStorage, With<GroundLayer>>,
-    ground_q: Query<&Ground>,
+    ground_q: Query<(&Ground, &SeedBank)>,
+    locale: Locale,
 ) {
     for mut text in &mut selected_ground_texts {
-        text.sections[0].value = String::from("None");
+        text.sections[0].value = locale.text("none").into();
 
         // Do we have anything selected?
-        if let Some(tile_pos) = selected_tile.0 {
+        if let Some(tile_pos) = shown_tile(&selected_tile, &inspected_tile) {
             if let Some(entity) = ground_tile_storage.single().get(&tile_pos) {
-                if let Ok(ground) = ground_q.get(entity) {
-                    let text_value = ground.name().to_string();
+                if let Ok((ground, seed_bank)) = ground_q.get(entity) {
+                    let mut text_value = match ground {
+                        Ground::Nutrient(seasons) => locale.format(
+                            "ground-nutrient-info",
+                            &[&locale.text(ground.name_key()), seasons],
+                        ),
+                        Ground::Normal => locale.text(ground.name_key()).into(),
+                    };
+                    if seed_bank.0 > 0 {
+                        text_value.push('\n');
+                        text_value.push_str(&locale.format("ground-seeds", &[&seed_bank.0]));
+                    }
                     text.sections[0].value.clone_from(&text_value);
                 }
             }
@@ -305,6 +353,18 @@ fn update_selected_ground_text(
     }
 }
 
+/// Outlines the left panel in the color of the selected tile.
+fn update_selected_tile_outlines(
+    accessibility: Res<Accessibility>,
+    mut outlines: Query<&mut Outline, Or<(With<SelectedTileTreeUi>, With<SelectedTileGroundUi>)>>,
+) {
+    for mut outline in &mut outlines {
+        if accessibility.is_changed() || outline.is_added() {
+            outline.color = accessibility.highlight_colors.selected();
+        }
+    }
+}
+
 fn middle_ui_root(parent: &mut ChildBuilder) {
     parent.spawn((
         Name::new("Middle Game UI"),
@@ -344,6 +404,8 @@ fn right_ui_root(parent: &mut ChildBuilder) {
             season_header_ui(parent);
             season_clock_ui(parent);
             season_description_ui(parent);
+            simulation_speed_ui(parent);
+            hint_ui(parent);
             season_action_ui(parent);
         });
 }
@@ -376,6 +438,7 @@ fn season_header_ui(parent: &mut ChildBuilder) {
                         ..default()
                     },
                 ),
+                ScaledText::new(40.0),
                 SeasonHeaderUi,
             ));
         });
@@ -384,9 +447,13 @@ fn season_header_ui(parent: &mut ChildBuilder) {
 fn update_season_header(
     season: Res<Season>,
     mut season_header_texts: Query<&mut Text, With<SeasonHeaderUi>>,
+    locale: Locale,
 ) {
     for mut text in &mut season_header_texts {
-        text.sections[0].value = format!("{}\nYear {}", season.kind.header(), season.year + 1);
+        text.sections[0].value = locale.format(
+            "season-header",
+            &[&locale.text(season.kind.header_key()), &(season.year + 1)],
+        );
     }
 }
 
@@ -400,7 +467,7 @@ fn season_clock_ui(parent: &mut ChildBuilder) {
             NodeBundle {
                 style: Style {
                     width: Percent(100.0),
-                    height: Percent(20.0),
+                    height: Percent(15.0),
                     ..default()
                 },
                 background_color: BackgroundColor(BROWN.into()),
@@ -417,6 +484,7 @@ fn season_clock_ui(parent: &mut ChildBuilder) {
                         ..default()
                     },
                 ),
+                ScaledText::new(40.0),
                 SeasonClockUi,
             ));
         });
@@ -425,9 +493,10 @@ fn season_clock_ui(parent: &mut ChildBuilder) {
 fn update_season_clock(
     score: Res<Score>,
     mut season_clock_texts: Query<&mut Text, With<SeasonClockUi>>,
+    locale: Locale,
 ) {
     for mut text in &mut season_clock_texts {
-        text.sections[0].value = format!("Score: {}", score.0);
+        text.sections[0].value = locale.format("score", &[&score.0]);
     }
 }
 
@@ -441,7 +510,7 @@ fn season_description_ui(parent: &mut ChildBuilder) {
             NodeBundle {
                 style: Style {
                     width: Percent(100.0),
-                    height: Percent(40.0),
+                    height: Percent(25.0),
                     ..default()
                 },
                 background_color: BackgroundColor(WHITE.into()),
@@ -458,6 +527,7 @@ fn season_description_ui(parent: &mut ChildBuilder) {
                         ..default()
                     },
                 ),
+                ScaledText::new(26.0),
                 SeasonDescriptionUi,
             ));
         });
@@ -466,9 +536,217 @@ fn season_description_ui(parent: &mut ChildBuilder) {
 fn update_season_description(
     season: Res<Season>,
     mut season_description_texts: Query<&mut Text, With<SeasonDescriptionUi>>,
+    locale: Locale,
 ) {
     for mut text in &mut season_description_texts {
-        text.sections[0].value = season.kind.description().into();
+        text.sections[0].value = locale.text(season.kind.description_key()).into();
+    }
+}
+
+#[derive(Debug, Component, Reflect)]
+pub struct SimulationSpeedUi(SimulationSpeed);
+
+fn simulation_speed_ui(parent: &mut ChildBuilder) {
+    parent
+        .spawn((
+            Name::new("Simulation Speed UI"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    height: Percent(10.0),
+                    justify_content: JustifyContent::SpaceEvenly,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: BackgroundColor(BROWN.into()),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            for speed in SimulationSpeed::ALL {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                width: Percent(22.0),
+                                height: Percent(80.0),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            background_color: BackgroundColor(NODE_BACKGROUND),
+                            ..default()
+                        },
+                        InteractionPalette {
+                            none: NODE_BACKGROUND,
+                            hovered: BUTTON_HOVERED_BACKGROUND,
+                            pressed: BUTTON_PRESSED_BACKGROUND,
+                        },
+                        Outline::new(Val::Px(3.0), Val::ZERO, Color::NONE),
+                        SimulationSpeedUi(speed),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            TextBundle::from_section(
+                                speed.label(),
+                                TextStyle {
+                                    font_size: 30.0,
+                                    color: BLACK.into(),
+                                    ..default()
+                                },
+                            ),
+                            ScaledText::new(30.0),
+                        ));
+                    });
+            }
+        });
+}
+
+fn update_simulation_speed_buttons(
+    speed: Res<SimulationSpeed>,
+    mut speed_buttons: Query<(&SimulationSpeedUi, &mut Outline)>,
+) {
+    for (speed_ui, mut outline) in &mut speed_buttons {
+        outline.color = if speed_ui.0 == *speed {
+            WHITE.into()
+        } else {
+            Color::NONE
+        };
+    }
+}
+
+fn handle_simulation_speed_action(
+    mut button_query: InteractionQuery<&SimulationSpeedUi>,
+    mut speed: ResMut<SimulationSpeed>,
+) {
+    for (interaction, speed_ui) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            *speed = speed_ui.0;
+        }
+    }
+}
+
+/// Space toggles pause, the number keys pick a speed.
+fn handle_simulation_speed_keys(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut speed: ResMut<SimulationSpeed>,
+    mut last_speed: Local<SimulationSpeed>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        if matches!(*speed, SimulationSpeed::Paused) {
+            *speed = *last_speed;
+        } else {
+            *last_speed = *speed;
+            *speed = SimulationSpeed::Paused;
+        }
+    }
+
+    for (key, key_speed) in [
+        (KeyCode::Digit1, SimulationSpeed::Normal),
+        (KeyCode::Digit2, SimulationSpeed::Double),
+        (KeyCode::Digit4, SimulationSpeed::Quadruple),
+    ] {
+        if keyboard_input.just_pressed(key) {
+            *speed = key_speed;
+        }
+    }
+}
+
+#[derive(Debug, Component, Reflect)]
+pub struct HintUi;
+
+#[derive(Debug, Component, Reflect)]
+pub struct HintText;
+
+fn hint_ui(parent: &mut ChildBuilder) {
+    parent
+        .spawn((
+            Name::new("Hint UI"),
+            NodeBundle {
+                style: Style {
+                    width: Percent(100.0),
+                    height: Percent(10.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: BackgroundColor(BROWN.into()),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            width: Percent(95.0),
+                            height: Percent(80.0),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        background_color: BackgroundColor(NODE_BACKGROUND),
+                        ..default()
+                    },
+                    InteractionPalette {
+                        none: NODE_BACKGROUND,
+                        hovered: BUTTON_HOVERED_BACKGROUND,
+                        pressed: BUTTON_PRESSED_BACKGROUND,
+                    },
+                    HintUi,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(
+                            "",
+                            TextStyle {
+                                font_size: 26.0,
+                                color: BLACK.into(),
+                                ..default()
+                            },
+                        ),
+                        ScaledText::new(26.0),
+                        LocalizedText::new("hint"),
+                        HintText,
+                    ));
+                });
+        });
+}
+
+/// Shows how many more points the hinted action is expected to bring than doing nothing.
+fn update_hint(hint: Option<Res<Hint>>, mut hint_texts: Query<&mut LocalizedText, With<HintText>>) {
+    for mut text in &mut hint_texts {
+        *text = match &hint {
+            Some(hint) => LocalizedText {
+                key: "hint-points".into(),
+                args: vec![format!("{:+.1}", hint.gain)],
+            },
+            None => LocalizedText::new("hint"),
+        };
+    }
+}
+
+fn handle_hint_action(mut commands: Commands, mut button_query: InteractionQuery<&HintUi>) {
+    for (interaction, _hint) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            commands.trigger(FindHint);
+        }
+    }
+}
+
+/// H or the north gamepad button ask for a hint.
+fn handle_hint_keys(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<ButtonInput<GamepadButton>>,
+) {
+    let pressed = keyboard_input.just_pressed(KeyCode::KeyH)
+        || gamepads.iter().any(|gamepad| {
+            gamepad_input.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::North))
+        });
+    if pressed {
+        commands.trigger(FindHint);
     }
 }
 
@@ -532,6 +810,7 @@ fn season_action_ui(parent: &mut ChildBuilder) {
                                 },
                             },
                         ]),
+                        ScaledText::new(40.0),
                         SeasonActionUi,
                     ));
                 });
@@ -541,17 +820,18 @@ fn season_action_ui(parent: &mut ChildBuilder) {
 fn update_season_action(
     season: Res<Season>,
     mut season_action_texts: Query<&mut Text, With<SeasonActionUi>>,
+    locale: Locale,
 ) {
     for mut text in &mut season_action_texts {
         if matches!(season.state, SeasonState::UserInput) {
-            text.sections[0].value = String::from("Action");
+            text.sections[0].value = locale.text("action").into();
             if season.user_action_resource > 0 {
                 text.sections[1].value = String::from("");
             } else {
-                text.sections[1].value = String::from("\nStart");
+                text.sections[1].value = format!("\n{}", locale.text("start"));
             }
         } else {
-            text.sections[0].value = String::from("Simulating");
+            text.sections[0].value = locale.text("simulating").into();
             text.sections[1].value = String::from("");
         }
     }
@@ -566,11 +846,55 @@ fn handle_season_action(
 ) {
     for (interaction, _action) in &mut button_query {
         if matches!(interaction, Interaction::Pressed) {
-            if season.user_action_resource > 0 {
-                season.kind.user_action(&mut commands);
-            } else if matches!(season.state, SeasonState::UserInput) {
-                next_season_state_events.send(NextSeasonState(season.state.next()));
-            }
+            press_season_action(&mut commands, &season, &mut next_season_state_events);
         }
     }
 }
+
+/// Enter or the south gamepad button act on the highlighted tile.
+/// Tab or the start gamepad button start the simulation once all actions are used.
+fn handle_season_action_keys(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<ButtonInput<GamepadButton>>,
+    season: Res<Season>,
+    highlighted_tile: Res<HighlightedTile>,
+    marked_tiles: Res<MarkedTiles>,
+    mut selected_tile: ResMut<SelectedTile>,
+    mut next_season_state_events: EventWriter<NextSeasonState>,
+) {
+    let gamepad_just_pressed = |button_type| {
+        gamepads
+            .iter()
+            .any(|gamepad| gamepad_input.just_pressed(GamepadButton::new(gamepad, button_type)))
+    };
+
+    let confirm = keyboard_input.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter])
+        || gamepad_just_pressed(GamepadButtonType::South);
+    if let Some(tile_pos) = highlighted_tile.0 {
+        if confirm && season.user_action_resource > 0 && marked_tiles.allows(&tile_pos) {
+            selected_tile.0 = Some(tile_pos);
+            press_season_action(&mut commands, &season, &mut next_season_state_events);
+        }
+    }
+
+    let start =
+        keyboard_input.just_pressed(KeyCode::Tab) || gamepad_just_pressed(GamepadButtonType::Start);
+    if start && season.user_action_resource == 0 {
+        press_season_action(&mut commands, &season, &mut next_season_state_events);
+    }
+}
+
+/// Does what the season action button shows: the season's action, or starting the simulation.
+fn press_season_action(
+    commands: &mut Commands,
+    season: &Season,
+    next_season_state_events: &mut EventWriter<NextSeasonState>,
+) {
+    if season.user_action_resource > 0 {
+        season.kind.user_action(commands);
+    } else if matches!(season.state, SeasonState::UserInput) {
+        next_season_state_events.send(NextSeasonState(season.state.next()));
+    }
+}
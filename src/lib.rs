@@ -1,15 +1,13 @@
 #[cfg(feature = "dev")]
 mod dev_tools;
-mod game;
+pub mod game;
+mod locale;
 mod screen;
+mod settings;
+mod storage;
 mod ui;
 
-use bevy::{
-    asset::AssetMetaCheck,
-    audio::{AudioPlugin, Volume},
-    log::LogPlugin,
-    prelude::*,
-};
+use bevy::{asset::AssetMetaCheck, log::LogPlugin, prelude::*, state::app::StatesPlugin};
 
 pub struct AppPlugin;
 
@@ -45,17 +43,17 @@ impl Plugin for AppPlugin {
                     .into(),
                     ..default()
                 })
-                .set(AudioPlugin {
-                    global_volume: GlobalVolume {
-                        volume: Volume::new(0.3),
-                    },
-                    ..default()
-                })
                 .disable::<LogPlugin>(),
         );
 
-        // Add other plugins.
-        app.add_plugins((game::plugin, screen::plugin, ui::plugin));
+        // Add other plugins. The saved settings come first, so they are not overwritten by defaults.
+        app.add_plugins((
+            settings::plugin,
+            game::plugin,
+            locale::plugin,
+            screen::plugin,
+            ui::plugin,
+        ));
 
         // Enable dev tools for dev builds.
         #[cfg(feature = "dev")]
@@ -63,6 +61,22 @@ impl Plugin for AppPlugin {
     }
 }
 
+/// The game's simulation without a window, rendering, audio, input or UI, for benchmarks.
+/// Add it next to `MinimalPlugins`: the level of [`MapSize`](game::spawn::level::MapSize),
+/// or the [`CustomLevel`](game::spawn::level::CustomLevel), spawns on the first update.
+/// Seasons are started with [`NextSeasonState`](game::season::state::NextSeasonState),
+/// like the button of the game UI does.
+pub struct HeadlessPlugin;
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((StatesPlugin, game::simulation_plugin));
+        app.init_resource::<game::assets::ImageAssets>();
+        app.init_resource::<ui::prelude::Accessibility>();
+        app.insert_state(screen::Screen::Playing);
+    }
+}
+
 /// High-level groupings of systems for the app in the `Update` schedule.
 /// When adding a new variant, make sure to order it in the `configure_sets`
 /// call above.
@@ -76,10 +90,18 @@ enum AppSet {
     Update,
 }
 
+/// Distance between the ears of the camera listening to spatial sounds, in world units.
+const SPATIAL_EAR_GAP: f32 = 64.0;
+
 fn spawn_camera(mut commands: Commands) {
     commands.spawn((
         Name::new("Camera"),
         Camera2dBundle::default(),
+        // Rodio pans spatial sounds towards the ear further away, so the ears are swapped.
+        SpatialListener {
+            left_ear_offset: Vec3::X * SPATIAL_EAR_GAP / 2.0,
+            right_ear_offset: Vec3::X * SPATIAL_EAR_GAP / -2.0,
+        },
         // Render all UI to this camera.
         // Not strictly necessary since we only use one camera,
         // but if we don't use this component, our UI will disappear as soon
@@ -0,0 +1,212 @@
+//! Translations of all player-facing text.
+//! Text is looked up by key in the current [`Language`], falling back to English and then the key itself.
+
+use std::fmt::Display;
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    ecs::system::SystemParam,
+    prelude::*,
+    utils::HashMap,
+};
+use bevy_asset_loader::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<Translation>();
+    app.init_asset_loader::<TranslationLoader>();
+    // Loaded right away, so even the loading screen is translated
+    app.init_collection::<LocaleAssets>();
+
+    app.register_type::<(Language, LocalizedText)>();
+    app.init_resource::<Language>();
+
+    app.add_systems(Startup, replace_default_font);
+    app.add_systems(Update, update_localized_texts);
+}
+
+/// Bevy's default font only covers ASCII, which is not enough for translations.
+fn replace_default_font(mut fonts: ResMut<Assets<Font>>) {
+    match Font::try_from_bytes(include_bytes!("../assets/fonts/Hack-Regular.ttf").to_vec()) {
+        Ok(font) => fonts.insert(&Handle::default(), font),
+        Err(error) => error!("Could not read the default font: {error}"),
+    }
+}
+
+#[derive(AssetCollection, Resource)]
+pub struct LocaleAssets {
+    #[asset(path = "locales/en.lang")]
+    pub english: Handle<Translation>,
+    #[asset(path = "locales/de.lang")]
+    pub german: Handle<Translation>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Resource, Reflect)]
+#[reflect(Resource)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::German];
+
+    /// The name of the language in itself.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+        }
+    }
+
+    /// Identifies the language in saved settings.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|language| language == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    fn translation<'a>(&self, locale_assets: &'a LocaleAssets) -> &'a Handle<Translation> {
+        match self {
+            Language::English => &locale_assets.english,
+            Language::German => &locale_assets.german,
+        }
+    }
+}
+
+/// Texts of one language by key.
+#[derive(Debug, Default, Asset, TypePath)]
+pub struct Translation(HashMap<String, String>);
+
+impl Translation {
+    /// Reads `key = value` lines. Lines starting with `#` are comments,
+    /// `\n` in a value is a line break.
+    fn parse(source: &str) -> Self {
+        Self(
+            source
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .filter_map(|line| line.split_once('='))
+                .map(|(key, value)| (key.trim().into(), value.trim().replace("\\n", "\n")))
+                .collect(),
+        )
+    }
+}
+
+#[derive(Default)]
+struct TranslationLoader;
+
+impl AssetLoader for TranslationLoader {
+    type Asset = Translation;
+    type Settings = ();
+    type Error = std::io::Error;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut source = String::new();
+        reader.read_to_string(&mut source).await?;
+        Ok(Translation::parse(&source))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["lang"]
+    }
+}
+
+/// Looks up translated text.
+#[derive(SystemParam)]
+pub struct Locale<'w> {
+    language: Res<'w, Language>,
+    locale_assets: Res<'w, LocaleAssets>,
+    translations: Res<'w, Assets<Translation>>,
+}
+
+impl Locale<'_> {
+    /// The text for `key` in the current language.
+    pub fn text<'a>(&'a self, key: &'a str) -> &'a str {
+        [*self.language, Language::English]
+            .into_iter()
+            .filter_map(|language| {
+                self.translations
+                    .get(language.translation(&self.locale_assets))
+            })
+            .find_map(|translation| translation.0.get(key))
+            .map_or(key, String::as_str)
+    }
+
+    /// Whether the English translation, which all languages fall back to, is loaded
+    /// and has no text for `key`. Empty keys are left for systems to fill in.
+    pub fn is_missing(&self, key: &str) -> bool {
+        !key.is_empty()
+            && self
+                .translations
+                .get(Language::English.translation(&self.locale_assets))
+                .is_some_and(|translation| !translation.0.contains_key(key))
+    }
+
+    /// The text for `key`, with `{0}`, `{1}`, ... replaced by `args`.
+    pub fn format(&self, key: &str, args: &[&dyn Display]) -> String {
+        args.iter()
+            .enumerate()
+            .fold(self.text(key).to_string(), |text, (index, arg)| {
+                text.replace(&format!("{{{index}}}"), &arg.to_string())
+            })
+    }
+}
+
+/// Text kept in the current language.
+/// Its first section shows the translation of `key`, with `args` filled in.
+/// Arguments that are keys are translated as well, others like numbers are shown as is.
+/// Text that is never translated is spawned without this, see `Widgets::label_raw`.
+#[derive(Debug, Component, Reflect)]
+#[reflect(Component)]
+pub struct LocalizedText {
+    pub key: String,
+    pub args: Vec<String>,
+}
+
+impl LocalizedText {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            args: Vec::new(),
+        }
+    }
+}
+
+fn update_localized_texts(
+    locale: Locale,
+    mut translation_events: EventReader<AssetEvent<Translation>>,
+    mut text_q: Query<(Ref<LocalizedText>, &mut Text)>,
+) {
+    let translations_changed = translation_events.read().count() > 0;
+
+    for (localized_text, mut text) in &mut text_q {
+        if translations_changed || locale.language.is_changed() || localized_text.is_changed() {
+            if locale.is_missing(&localized_text.key) {
+                warn!("No translation for {:?}", localized_text.key);
+            }
+            let args: Vec<&str> = localized_text
+                .args
+                .iter()
+                .map(|arg| locale.text(arg))
+                .collect();
+            let args: Vec<&dyn Display> = args.iter().map(|arg| arg as &dyn Display).collect();
+            text.sections[0].value = locale.format(&localized_text.key, &args);
+        }
+    }
+}
@@ -30,19 +30,20 @@ fn enter_credits(mut commands: Commands, soundtrack_assets: Res<SoundtrackAssets
         .ui_root()
         .insert(StateScoped(Screen::Credits))
         .with_children(|children| {
-            children.header("Made by");
-            children.label("KirmesBude");
+            children.header("credits-made-by");
+            children.label_raw("KirmesBude");
 
-            children.header("Assets");
-            children.label("Bevy logo - All rights reserved by the Bevy Foundation. Permission granted for splash screen use when unmodified.");
-            children.label("Music - CC BY 4.0 by Vindsvept");
+            children.header("credits-assets");
+            children.label("credits-bevy-logo");
+            children.label("credits-music");
+            children.label("credits-font");
 
-            children.header("Plugins");
-            children.label("bevy_rand - by Bluefinger");
-            children.label("bevy-inspector-egui - by jakobhellermann");
-            children.label("bevy_ecs_tilemap - by StarArawn");
+            children.header("credits-plugins");
+            children.label("credits-bevy-rand");
+            children.label("credits-bevy-inspector-egui");
+            children.label("credits-bevy-ecs-tilemap");
 
-            children.button("Back").insert(CreditsAction::Back);
+            children.button("back").insert(CreditsAction::Back);
         });
 
     commands.trigger(PlaySoundtrack::Handle(
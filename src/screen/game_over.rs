@@ -4,7 +4,13 @@ use bevy::prelude::*;
 
 use super::Screen;
 use crate::{
-    game::{assets::SoundtrackAssets, audio::soundtrack::PlaySoundtrack, Score},
+    game::{
+        assets::SoundtrackAssets,
+        audio::soundtrack::PlaySoundtrack,
+        daily::{Daily, DailyRecord},
+        puzzle::ActivePuzzle,
+        Score,
+    },
     ui::prelude::*,
 };
 
@@ -27,6 +33,9 @@ enum GameOverAction {
 
 fn enter_game_over(
     score: Res<Score>,
+    active_puzzle: Option<Res<ActivePuzzle>>,
+    daily: Option<Res<Daily>>,
+    daily_record: Res<DailyRecord>,
     mut commands: Commands,
     soundtrack_assets: Res<SoundtrackAssets>,
 ) {
@@ -34,12 +43,22 @@ fn enter_game_over(
         .ui_root()
         .insert(StateScoped(Screen::GameOver))
         .with_children(|children| {
-            children.header("GAME OVER");
+            children.header("game-over");
 
-            children.header("Score:");
-            children.label(format!("{}", score.0));
+            children.header("game-over-score");
+            children.label_raw(score.0.to_string());
 
-            children.button("Back").insert(GameOverAction::Back);
+            if let Some(active_puzzle) = active_puzzle {
+                children.header("game-over-stars");
+                children.label_raw(format!("{} / 3", active_puzzle.puzzle().stars_for(score.0)));
+            }
+
+            if let Some(daily) = daily {
+                children.header("game-over-streak");
+                children.label_raw(daily_record.streak_on(daily.day).to_string());
+            }
+
+            children.button("back").insert(GameOverAction::Back);
         });
 
     commands.trigger(PlaySoundtrack::Handle(
@@ -1,8 +1,8 @@
-//! A credits screen that can be accessed from the title screen.
+//! A how to play screen that can be accessed from the title screen and the pause menu.
 
 use bevy::prelude::*;
 
-use super::Screen;
+use super::{pause::pause_overlay, Pause, Screen};
 use crate::{
     game::{assets::SoundtrackAssets, audio::soundtrack::PlaySoundtrack},
     ui::prelude::*,
@@ -11,10 +11,12 @@ use crate::{
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::HowToPlay), enter_how_to_play);
     app.add_systems(OnExit(Screen::HowToPlay), exit_how_to_play);
+    app.add_systems(OnEnter(Pause::HowToPlay), enter_pause_how_to_play);
 
     app.add_systems(
         Update,
-        handle_how_to_play_action.run_if(in_state(Screen::HowToPlay)),
+        handle_how_to_play_action
+            .run_if(in_state(Screen::HowToPlay).or_else(in_state(Pause::HowToPlay))),
     );
     app.register_type::<HowToPlayAction>();
 }
@@ -29,36 +31,59 @@ fn enter_how_to_play(mut commands: Commands, soundtrack_assets: Res<SoundtrackAs
     commands
         .ui_root()
         .insert(StateScoped(Screen::HowToPlay))
-        .with_children(|children| {
-            children.header("Gain as many points as possible in 3 years:");
-            children.label("Each winter you gain 5 points for each mature tree felled and 6 points for each overmature tree felled,");
-            children.label("Points are tripled if the corresponding tree is on nutrient soil.");
-
-            children.header("Tree logic:");
-            children.label("Apart from winter, trees will always try to grow. They can do so if the level of their 8 neighbour trees does not exceed a level of 2.");
-            children.label("Apart from summer, trees can die due to overcrowding. They do so if the level of their 8 neighbour trees exceeds a level of 4.");
-            children.label("Seedling, immature and mature are level 1, while overmature is level 2.");
-
-            children.button("Back").insert(HowToPlayAction::Back);
-        });
+        .with_children(spawn_how_to_play);
 
     commands.trigger(PlaySoundtrack::Handle(
         soundtrack_assets.credits.clone_weak(),
     ));
 }
 
+fn enter_pause_how_to_play(mut commands: Commands) {
+    pause_overlay(&mut commands, Pause::HowToPlay).with_children(spawn_how_to_play);
+}
+
+fn spawn_how_to_play(children: &mut ChildBuilder) {
+    children.header("how-to-play-goal");
+    children.label("how-to-play-felling");
+    children.label("how-to-play-nutrient-points");
+    children.label("how-to-play-nutrient-soil");
+
+    children.header("how-to-play-tree-logic");
+    children.label("how-to-play-growing");
+    children.label("how-to-play-overcrowding");
+    children.label("how-to-play-old-age");
+    children.label("how-to-play-pests");
+    children.label("how-to-play-levels");
+    children.label("how-to-play-seeds");
+
+    children.header("how-to-play-controls");
+    children.label("how-to-play-speed");
+    children.label("how-to-play-cursor");
+    children.label("how-to-play-view");
+    children.label("how-to-play-touch");
+    children.label("how-to-play-hint");
+    children.label("how-to-play-pause");
+
+    children.button("back").insert(HowToPlayAction::Back);
+}
+
 fn exit_how_to_play(mut commands: Commands) {
     commands.trigger(PlaySoundtrack::Disable);
 }
 
 fn handle_how_to_play_action(
+    screen: Res<State<Screen>>,
     mut next_screen: ResMut<NextState<Screen>>,
+    mut next_pause: ResMut<NextState<Pause>>,
     mut button_query: InteractionQuery<&HowToPlayAction>,
 ) {
     for (interaction, action) in &mut button_query {
         if matches!(interaction, Interaction::Pressed) {
             match action {
-                HowToPlayAction::Back => next_screen.set(Screen::Title),
+                HowToPlayAction::Back => match screen.get() {
+                    Screen::Playing => next_pause.set(Pause::Menu),
+                    _ => next_screen.set(Screen::Title),
+                },
             }
         }
     }
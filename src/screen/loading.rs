@@ -48,6 +48,6 @@ fn enter_loading(
         .ui_root()
         .insert(StateScoped(Screen::Loading))
         .with_children(|children| {
-            children.label("Loading...");
+            children.label("loading");
         });
 }
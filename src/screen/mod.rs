@@ -4,7 +4,10 @@ mod credits;
 mod game_over;
 mod how_to_play;
 mod loading;
+mod pause;
 mod playing;
+mod puzzles;
+mod settings;
 mod splash;
 mod title;
 
@@ -13,6 +16,8 @@ use bevy::prelude::*;
 pub(super) fn plugin(app: &mut App) {
     app.init_state::<Screen>();
     app.enable_state_scoped_entities::<Screen>();
+    app.add_sub_state::<Pause>();
+    app.enable_state_scoped_entities::<Pause>();
 
     app.add_plugins((
         splash::plugin,
@@ -20,8 +25,11 @@ pub(super) fn plugin(app: &mut App) {
         title::plugin,
         credits::plugin,
         playing::plugin,
+        puzzles::plugin,
+        pause::plugin,
         game_over::plugin,
         how_to_play::plugin,
+        settings::plugin,
     ));
 }
 
@@ -32,8 +40,24 @@ pub enum Screen {
     Splash,
     Loading,
     Title,
+    Puzzles,
     Credits,
     Playing,
     GameOver,
     HowToPlay,
+    Settings,
+    /// Passed through to start [`Screen::Playing`] over, as a state can not be entered again directly.
+    Restart,
+}
+
+/// Whether the game is running or paused, and which page of the pause menu is open.
+#[derive(SubStates, Debug, Hash, PartialEq, Eq, Clone, Default)]
+#[source(Screen = Screen::Playing)]
+pub enum Pause {
+    #[default]
+    Running,
+    Menu,
+    Settings,
+    HowToPlay,
+    ConfirmQuit,
 }
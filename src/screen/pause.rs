@@ -0,0 +1,129 @@
+//! The pause menu, opened with Escape while playing. Game time stands still while it is open.
+
+use bevy::{ecs::system::EntityCommands, prelude::*, ui::FocusPolicy};
+
+use super::{Pause, Screen};
+use crate::ui::{palette::OVERLAY_BACKGROUND, prelude::*};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnExit(Pause::Running), pause_time);
+    app.add_systems(OnEnter(Pause::Running), unpause_time);
+    // Quitting from the pause menu leaves it without running the game again
+    app.add_systems(OnExit(Screen::Playing), unpause_time);
+
+    app.add_systems(OnEnter(Pause::Menu), enter_pause_menu);
+    app.add_systems(OnEnter(Pause::ConfirmQuit), enter_confirm_quit);
+
+    app.add_systems(
+        Update,
+        (
+            toggle_pause.run_if(in_state(Screen::Playing)),
+            handle_pause_action.run_if(in_state(Pause::Menu).or_else(in_state(Pause::ConfirmQuit))),
+        ),
+    );
+    app.register_type::<PauseAction>();
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum PauseAction {
+    Resume,
+    Restart,
+    Settings,
+    HowToPlay,
+    Quit,
+    ConfirmQuit,
+    CancelQuit,
+}
+
+/// Spawns a root node for a page of the pause menu, covering and blocking the game behind it.
+pub(super) fn pause_overlay<'a>(commands: &'a mut Commands, page: Pause) -> EntityCommands<'a> {
+    let mut overlay = commands.ui_root();
+    overlay.insert((
+        Name::new("Pause Overlay"),
+        BackgroundColor(OVERLAY_BACKGROUND),
+        FocusPolicy::Block,
+        ZIndex::Global(1),
+        StateScoped(page),
+    ));
+    overlay
+}
+
+/// Escape or the select gamepad button open the pause menu, close it, or go back to it.
+fn toggle_pause(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_input: Res<ButtonInput<GamepadButton>>,
+    pause: Res<State<Pause>>,
+    mut next_pause: ResMut<NextState<Pause>>,
+) {
+    let pressed = keyboard_input.just_pressed(KeyCode::Escape)
+        || gamepads.iter().any(|gamepad| {
+            gamepad_input.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::Select))
+        });
+    if pressed {
+        next_pause.set(match pause.get() {
+            Pause::Running => Pause::Menu,
+            Pause::Menu => Pause::Running,
+            Pause::Settings | Pause::HowToPlay | Pause::ConfirmQuit => Pause::Menu,
+        });
+    }
+}
+
+fn pause_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+fn unpause_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+fn enter_pause_menu(mut commands: Commands) {
+    pause_overlay(&mut commands, Pause::Menu).with_children(|children| {
+        children.header("pause-title");
+        children.button("pause-resume").insert(PauseAction::Resume);
+        children
+            .button("pause-restart")
+            .insert(PauseAction::Restart);
+        children
+            .button("title-settings")
+            .insert(PauseAction::Settings);
+        children
+            .button("title-how-to-play")
+            .insert(PauseAction::HowToPlay);
+        children.button("pause-quit").insert(PauseAction::Quit);
+    });
+}
+
+fn enter_confirm_quit(mut commands: Commands) {
+    pause_overlay(&mut commands, Pause::ConfirmQuit).with_children(|children| {
+        children.label("pause-confirm-quit");
+        children
+            .button("pause-quit-confirm")
+            .insert(PauseAction::ConfirmQuit);
+        children
+            .button("pause-quit-cancel")
+            .insert(PauseAction::CancelQuit);
+    });
+}
+
+fn handle_pause_action(
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut next_pause: ResMut<NextState<Pause>>,
+    mut button_query: InteractionQuery<&PauseAction>,
+) {
+    for (interaction, action) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            match action {
+                PauseAction::Resume => next_pause.set(Pause::Running),
+                // Keeps the level seed, so the run starts over the same way
+                PauseAction::Restart => next_screen.set(Screen::Restart),
+                PauseAction::Settings => next_pause.set(Pause::Settings),
+                PauseAction::HowToPlay => next_pause.set(Pause::HowToPlay),
+                PauseAction::Quit => next_pause.set(Pause::ConfirmQuit),
+                PauseAction::ConfirmQuit => next_screen.set(Screen::Title),
+                PauseAction::CancelQuit => next_pause.set(Pause::Menu),
+            }
+        }
+    }
+}
@@ -1,38 +1,33 @@
 //! The screen state for the main game loop.
 
-use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use bevy::prelude::*;
 
 use super::Screen;
 use crate::game::{
-    assets::SoundtrackAssets, audio::soundtrack::PlaySoundtrack, season::Season,
-    spawn::level::SpawnLevel, ui::SpawnGameUi, Score,
+    audio::soundtrack::{PlaySeasonSoundtrack, PlaySoundtrack},
+    season::SimulationSpeed,
+    spawn::level::MarkedTiles,
+    ui::SpawnGameUi,
 };
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Playing), enter_playing);
     app.add_systems(OnExit(Screen::Playing), exit_playing);
-
-    app.add_systems(
-        Update,
-        return_to_title_screen
-            .run_if(in_state(Screen::Playing).and_then(input_just_pressed(KeyCode::Escape))),
-    );
+    app.add_systems(OnEnter(Screen::Restart), restart_playing);
 }
 
+/// The level itself is started by the game, see `game::start_level`.
 fn enter_playing(
     mut commands: Commands,
-    soundtrack_assets: Res<SoundtrackAssets>,
-    mut score: ResMut<Score>,
-    mut season: ResMut<Season>,
+    mut speed: ResMut<SimulationSpeed>,
+    mut marked_tiles: ResMut<MarkedTiles>,
 ) {
     commands.trigger(SpawnGameUi);
-    commands.trigger(SpawnLevel);
-    commands.trigger(PlaySoundtrack::Handle(
-        soundtrack_assets.gameplay.clone_weak(),
-    ));
 
-    *score = Score::default();
-    *season = Season::default();
+    *speed = SimulationSpeed::default();
+    *marked_tiles = MarkedTiles::default();
+
+    commands.trigger(PlaySeasonSoundtrack);
 }
 
 fn exit_playing(mut commands: Commands) {
@@ -40,6 +35,6 @@ fn exit_playing(mut commands: Commands) {
     commands.trigger(PlaySoundtrack::Disable);
 }
 
-fn return_to_title_screen(mut next_screen: ResMut<NextState<Screen>>) {
-    next_screen.set(Screen::Title);
+fn restart_playing(mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Playing);
 }
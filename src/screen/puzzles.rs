@@ -0,0 +1,95 @@
+//! A screen to pick a puzzle, showing the stars earned in each.
+
+use bevy::prelude::*;
+
+use super::Screen;
+use crate::{
+    game::{
+        assets::SoundtrackAssets,
+        audio::soundtrack::PlaySoundtrack,
+        puzzle::{PuzzleProgress, StartPuzzle, PUZZLES},
+    },
+    locale::LocalizedText,
+    ui::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Puzzles), enter_puzzles);
+    app.add_systems(OnExit(Screen::Puzzles), exit_puzzles);
+
+    app.add_systems(
+        Update,
+        (handle_puzzles_action, update_puzzle_labels).run_if(in_state(Screen::Puzzles)),
+    );
+    app.register_type::<PuzzlesAction>();
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum PuzzlesAction {
+    /// Index into [`PUZZLES`].
+    Play(usize),
+    Back,
+}
+
+fn enter_puzzles(mut commands: Commands, soundtrack_assets: Res<SoundtrackAssets>) {
+    commands
+        .ui_root()
+        .insert(StateScoped(Screen::Puzzles))
+        .with_children(|children| {
+            children.header("puzzles");
+            for index in 0..PUZZLES.len() {
+                children.button("").insert(PuzzlesAction::Play(index));
+            }
+
+            children.button("back").insert(PuzzlesAction::Back);
+        });
+
+    commands.trigger(PlaySoundtrack::Handle(
+        soundtrack_assets.credits.clone_weak(),
+    ));
+}
+
+fn exit_puzzles(mut commands: Commands) {
+    commands.trigger(PlaySoundtrack::Disable);
+}
+
+fn handle_puzzles_action(
+    mut commands: Commands,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut button_query: InteractionQuery<&PuzzlesAction>,
+) {
+    for (interaction, action) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            match action {
+                PuzzlesAction::Play(index) => commands.trigger(StartPuzzle(*index)),
+                PuzzlesAction::Back => next_screen.set(Screen::Title),
+            }
+        }
+    }
+}
+
+/// Names each puzzle button with the stars earned so far.
+fn update_puzzle_labels(
+    progress: Res<PuzzleProgress>,
+    button_query: Query<(Ref<PuzzlesAction>, &Children)>,
+    mut text_query: Query<&mut LocalizedText>,
+) {
+    for (action, children) in &button_query {
+        let PuzzlesAction::Play(index) = *action else {
+            continue;
+        };
+        if progress.is_changed() || action.is_added() {
+            let mut texts = text_query.iter_many_mut(children);
+            while let Some(mut text) = texts.fetch_next() {
+                *text = LocalizedText {
+                    key: "puzzle-button".into(),
+                    args: vec![
+                        PUZZLES[index].name_key.into(),
+                        progress.0[index].to_string(),
+                    ],
+                };
+            }
+        }
+    }
+}
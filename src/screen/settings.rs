@@ -0,0 +1,270 @@
+//! A settings screen that can be accessed from the title screen and the pause menu.
+//! Settings are saved when leaving it.
+
+use bevy::{ecs::system::EntityCommands, prelude::*};
+
+use super::{pause::pause_overlay, Pause, Screen};
+use crate::{
+    game::{assets::SoundtrackAssets, audio::soundtrack::PlaySoundtrack, spawn::level::MapSize},
+    locale::{Language, LocalizedText},
+    settings::{AudioSettings, DisplaySettings},
+    ui::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Settings), enter_settings);
+    app.add_systems(OnExit(Screen::Settings), exit_settings);
+    app.add_systems(OnEnter(Pause::Settings), enter_pause_settings);
+
+    app.add_systems(
+        Update,
+        (
+            handle_settings_action,
+            handle_settings_sliders,
+            update_settings_labels,
+        )
+            .chain()
+            .run_if(in_state(Screen::Settings).or_else(in_state(Pause::Settings))),
+    );
+    app.register_type::<SettingsAction>();
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+enum SettingsAction {
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+    Fullscreen,
+    VSync,
+    Language,
+    HighlightColors,
+    TileOutlines,
+    TextScale,
+    MapSize,
+    Back,
+}
+
+impl SettingsAction {
+    /// Translation key and arguments of the button text, showing the current value.
+    fn text(
+        &self,
+        language: Language,
+        audio_settings: &AudioSettings,
+        display_settings: &DisplaySettings,
+        accessibility: &Accessibility,
+        map_size: MapSize,
+    ) -> LocalizedText {
+        let percent = |value: f32| (value * 100.0).round().to_string();
+        let on_off = |value: bool| if value { "on" } else { "off" }.to_string();
+        let (key, args) = match self {
+            SettingsAction::MasterVolume => (
+                "settings-master-volume",
+                vec![percent(audio_settings.master)],
+            ),
+            SettingsAction::MusicVolume => {
+                ("settings-music-volume", vec![percent(audio_settings.music)])
+            }
+            SettingsAction::SfxVolume => ("settings-sfx-volume", vec![percent(audio_settings.sfx)]),
+            SettingsAction::Fullscreen => (
+                "settings-fullscreen",
+                vec![on_off(display_settings.fullscreen)],
+            ),
+            SettingsAction::VSync => ("settings-vsync", vec![on_off(display_settings.vsync)]),
+            SettingsAction::Language => ("settings-language", vec![language.name().into()]),
+            SettingsAction::HighlightColors => (
+                "settings-colors",
+                vec![accessibility.highlight_colors.name_key().into()],
+            ),
+            SettingsAction::TileOutlines => (
+                "settings-tile-outlines",
+                vec![on_off(accessibility.tile_outlines)],
+            ),
+            SettingsAction::TextScale => (
+                "settings-text-size",
+                vec![percent(accessibility.text_scale)],
+            ),
+            SettingsAction::MapSize => ("settings-map-size", vec![map_size.0.to_string()]),
+            SettingsAction::Back => ("back", vec![]),
+        };
+        LocalizedText {
+            key: key.into(),
+            args,
+        }
+    }
+}
+
+fn enter_settings(
+    mut commands: Commands,
+    soundtrack_assets: Res<SoundtrackAssets>,
+    audio_settings: Res<AudioSettings>,
+) {
+    commands
+        .ui_root()
+        .insert(StateScoped(Screen::Settings))
+        .with_children(|children| spawn_settings(children, &audio_settings));
+
+    commands.trigger(PlaySoundtrack::Handle(
+        soundtrack_assets.credits.clone_weak(),
+    ));
+}
+
+fn enter_pause_settings(mut commands: Commands, audio_settings: Res<AudioSettings>) {
+    pause_overlay(&mut commands, Pause::Settings)
+        .with_children(|children| spawn_settings(children, &audio_settings));
+}
+
+fn spawn_settings(children: &mut ChildBuilder, audio_settings: &AudioSettings) {
+    children
+        .spawn((
+            Name::new("Settings Columns"),
+            NodeBundle {
+                style: Style {
+                    column_gap: Val::Px(40.0),
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .with_children(|children| {
+            settings_column(children).with_children(|children| {
+                children.header("settings-audio");
+                for (action, volume) in [
+                    (SettingsAction::MasterVolume, audio_settings.master),
+                    (SettingsAction::MusicVolume, audio_settings.music),
+                    (SettingsAction::SfxVolume, audio_settings.sfx),
+                ] {
+                    children.slider("", volume).insert(action);
+                }
+
+                children.header("settings-display");
+                children.button("").insert(SettingsAction::Fullscreen);
+                children.button("").insert(SettingsAction::VSync);
+
+                children.header("settings-game");
+                children.button("").insert(SettingsAction::MapSize);
+            });
+
+            settings_column(children).with_children(|children| {
+                children.button("").insert(SettingsAction::Language);
+
+                children.header("settings-accessibility");
+                for action in [
+                    SettingsAction::HighlightColors,
+                    SettingsAction::TileOutlines,
+                    SettingsAction::TextScale,
+                ] {
+                    children.button("").insert(action);
+                }
+            });
+        });
+
+    children.button("back").insert(SettingsAction::Back);
+}
+
+fn settings_column<'a>(children: &'a mut ChildBuilder) -> EntityCommands<'a> {
+    children.spawn((
+        Name::new("Settings Column"),
+        NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            ..default()
+        },
+    ))
+}
+
+fn exit_settings(mut commands: Commands) {
+    commands.trigger(PlaySoundtrack::Disable);
+}
+
+fn handle_settings_action(
+    screen: Res<State<Screen>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut next_pause: ResMut<NextState<Pause>>,
+    mut button_query: InteractionQuery<&SettingsAction>,
+    mut display_settings: ResMut<DisplaySettings>,
+    mut language: ResMut<Language>,
+    mut accessibility: ResMut<Accessibility>,
+    mut map_size: ResMut<MapSize>,
+) {
+    for (interaction, action) in &mut button_query {
+        if matches!(interaction, Interaction::Pressed) {
+            match action {
+                // Set by dragging, see `handle_settings_sliders`
+                SettingsAction::MasterVolume
+                | SettingsAction::MusicVolume
+                | SettingsAction::SfxVolume => {}
+                SettingsAction::Fullscreen => {
+                    display_settings.fullscreen = !display_settings.fullscreen;
+                }
+                SettingsAction::VSync => display_settings.vsync = !display_settings.vsync,
+                SettingsAction::Language => *language = language.next(),
+                SettingsAction::HighlightColors => {
+                    accessibility.highlight_colors = accessibility.highlight_colors.next();
+                }
+                SettingsAction::TileOutlines => {
+                    accessibility.tile_outlines = !accessibility.tile_outlines;
+                }
+                SettingsAction::TextScale => {
+                    accessibility.text_scale = accessibility.next_text_scale();
+                }
+                // Applies from the next game on
+                SettingsAction::MapSize => *map_size = map_size.next(),
+                SettingsAction::Back => match screen.get() {
+                    Screen::Playing => next_pause.set(Pause::Menu),
+                    _ => next_screen.set(Screen::Title),
+                },
+            }
+        }
+    }
+}
+
+fn handle_settings_sliders(
+    slider_query: Query<(&Slider, &SettingsAction), Changed<Slider>>,
+    mut audio_settings: ResMut<AudioSettings>,
+) {
+    let mut new_settings = *audio_settings;
+    for (slider, action) in &slider_query {
+        match action {
+            SettingsAction::MasterVolume => new_settings.master = slider.value,
+            SettingsAction::MusicVolume => new_settings.music = slider.value,
+            SettingsAction::SfxVolume => new_settings.sfx = slider.value,
+            _ => {}
+        }
+    }
+    audio_settings.set_if_neq(new_settings);
+}
+
+fn update_settings_labels(
+    language: Res<Language>,
+    audio_settings: Res<AudioSettings>,
+    display_settings: Res<DisplaySettings>,
+    accessibility: Res<Accessibility>,
+    map_size: Res<MapSize>,
+    button_query: Query<(Ref<SettingsAction>, &Children)>,
+    mut text_query: Query<&mut LocalizedText>,
+) {
+    let settings_changed = language.is_changed()
+        || audio_settings.is_changed()
+        || display_settings.is_changed()
+        || accessibility.is_changed()
+        || map_size.is_changed();
+    for (action, children) in &button_query {
+        if settings_changed || action.is_added() {
+            let mut texts = text_query.iter_many_mut(children);
+            while let Some(mut text) = texts.fetch_next() {
+                *text = action.text(
+                    *language,
+                    &audio_settings,
+                    &display_settings,
+                    &accessibility,
+                    *map_size,
+                );
+            }
+        }
+    }
+}
@@ -4,7 +4,15 @@ use bevy::prelude::*;
 
 use super::Screen;
 use crate::{
-    game::{assets::SoundtrackAssets, audio::soundtrack::PlaySoundtrack},
+    game::{
+        ai::{Demo, Strategy},
+        assets::SoundtrackAssets,
+        audio::soundtrack::PlaySoundtrack,
+        daily::{format_date, today, DailyRecord, StartDaily},
+        tutorial::{Tutorial, TUTORIAL_SEED},
+        LevelSeed,
+    },
+    locale::LocalizedText,
     ui::prelude::*,
 };
 
@@ -12,15 +20,23 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Title), enter_title);
     app.add_systems(OnEnter(Screen::Title), exit_title);
 
-    app.register_type::<TitleAction>();
-    app.add_systems(Update, handle_title_action.run_if(in_state(Screen::Title)));
+    app.register_type::<(TitleAction, DailyLabel)>();
+    app.add_systems(
+        Update,
+        (handle_title_action, update_daily_label).run_if(in_state(Screen::Title)),
+    );
 }
 
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
 #[reflect(Component)]
 enum TitleAction {
     Play,
+    Tutorial,
+    Puzzles,
+    Daily,
+    Demo,
     HowToPlay,
+    Settings,
     Credits,
     /// Exit doesn't work well with embedded applications.
     #[cfg(not(target_family = "wasm"))]
@@ -32,27 +48,69 @@ fn enter_title(mut commands: Commands, soundtrack_assets: Res<SoundtrackAssets>)
         .ui_root()
         .insert(StateScoped(Screen::Title))
         .with_children(|children| {
-            children.button("Play").insert(TitleAction::Play);
-            children.button("HowToPlay").insert(TitleAction::HowToPlay);
-            children.button("Credits").insert(TitleAction::Credits);
+            children.button("title-play").insert(TitleAction::Play);
+            children
+                .button("title-tutorial")
+                .insert(TitleAction::Tutorial);
+            children
+                .button("title-puzzles")
+                .insert(TitleAction::Puzzles);
+            children.button("title-daily").insert(TitleAction::Daily);
+            children.label("daily-not-played").insert(DailyLabel);
+            children.button("title-demo").insert(TitleAction::Demo);
+            children
+                .button("title-how-to-play")
+                .insert(TitleAction::HowToPlay);
+            children
+                .button("title-settings")
+                .insert(TitleAction::Settings);
+            children
+                .button("title-credits")
+                .insert(TitleAction::Credits);
 
             #[cfg(not(target_family = "wasm"))]
-            children.button("Exit").insert(TitleAction::Exit);
+            children.button("title-exit").insert(TitleAction::Exit);
         });
 
     commands.trigger(PlaySoundtrack::Handle(soundtrack_assets.title.clone_weak()));
 }
 
 fn handle_title_action(
+    mut commands: Commands,
     mut next_screen: ResMut<NextState<Screen>>,
     mut button_query: InteractionQuery<&TitleAction>,
+    mut level_seed: ResMut<LevelSeed>,
+    mut demos: Local<usize>,
     #[cfg(not(target_family = "wasm"))] mut app_exit: EventWriter<AppExit>,
 ) {
     for (interaction, action) in &mut button_query {
         if matches!(interaction, Interaction::Pressed) {
             match action {
-                TitleAction::Play => next_screen.set(Screen::Playing),
+                TitleAction::Play => {
+                    commands.remove_resource::<Tutorial>();
+                    commands.remove_resource::<Demo>();
+                    *level_seed = LevelSeed::random();
+                    next_screen.set(Screen::Playing);
+                }
+                TitleAction::Tutorial => {
+                    commands.remove_resource::<Demo>();
+                    commands.insert_resource(Tutorial::default());
+                    *level_seed = LevelSeed(TUTORIAL_SEED);
+                    next_screen.set(Screen::Playing);
+                }
+                TitleAction::Puzzles => next_screen.set(Screen::Puzzles),
+                TitleAction::Daily => commands.trigger(StartDaily),
+                // Each demo shows the next strategy
+                TitleAction::Demo => {
+                    commands.remove_resource::<Tutorial>();
+                    let strategy = Strategy::ALL[*demos % Strategy::ALL.len()];
+                    *demos += 1;
+                    commands.insert_resource(Demo::new(strategy));
+                    *level_seed = LevelSeed::random();
+                    next_screen.set(Screen::Playing);
+                }
                 TitleAction::HowToPlay => next_screen.set(Screen::HowToPlay),
+                TitleAction::Settings => next_screen.set(Screen::Settings),
                 TitleAction::Credits => next_screen.set(Screen::Credits),
 
                 #[cfg(not(target_family = "wasm"))]
@@ -64,6 +122,44 @@ fn handle_title_action(
     }
 }
 
+/// Shows today's result of the daily challenge and the streak.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+struct DailyLabel;
+
+fn update_daily_label(
+    record: Res<DailyRecord>,
+    label_query: Query<&Children, Added<DailyLabel>>,
+    mut text_query: Query<&mut LocalizedText>,
+) {
+    let day = today();
+    let (key, args) = if record.played(day) {
+        (
+            "daily-today",
+            vec![
+                format_date(day),
+                record.score.to_string(),
+                record.streak.to_string(),
+            ],
+        )
+    } else {
+        (
+            "daily-not-played",
+            vec![format_date(day), record.streak_on(day).to_string()],
+        )
+    };
+
+    for children in &label_query {
+        let mut texts = text_query.iter_many_mut(children);
+        while let Some(mut text) = texts.fetch_next() {
+            *text = LocalizedText {
+                key: key.into(),
+                args: args.clone(),
+            };
+        }
+    }
+}
+
 fn exit_title(mut commands: Commands) {
     commands.trigger(PlaySoundtrack::Disable);
 }
@@ -0,0 +1,232 @@
+//! Audio and display settings. All settings, including the language and accessibility,
+//! are saved to a config file and applied at startup.
+
+use bevy::{
+    audio::Volume,
+    prelude::*,
+    window::{PresentMode, PrimaryWindow, WindowMode},
+};
+
+use crate::{
+    game::spawn::level::MapSize,
+    locale::Language,
+    screen::{Pause, Screen},
+    storage::{self, Entries},
+    ui::accessibility::{Accessibility, HighlightColors, TEXT_SCALES},
+};
+
+/// Loads the saved settings. Add this before the plugins initializing
+/// [`Language`], [`Accessibility`] and [`MapSize`], so their defaults do not win.
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(AudioSettings, VolumeChannel, DisplaySettings)>();
+
+    let entries = storage::load(SETTINGS_FILE);
+    let audio_settings = AudioSettings::load(&entries);
+    app.insert_resource(GlobalVolume::new(audio_settings.master));
+    app.insert_resource(audio_settings);
+    app.insert_resource(DisplaySettings::load(&entries));
+    app.insert_resource(load_language(&entries));
+    app.insert_resource(load_accessibility(&entries));
+    app.insert_resource(load_map_size(&entries));
+
+    app.add_systems(
+        Update,
+        (
+            apply_volume.run_if(resource_changed::<AudioSettings>),
+            apply_display_settings.run_if(resource_changed::<DisplaySettings>),
+        ),
+    );
+    app.add_systems(OnExit(Screen::Settings), save_settings);
+    app.add_systems(OnExit(Pause::Settings), save_settings);
+}
+
+const SETTINGS_FILE: &str = "settings.cfg";
+
+/// Volumes from 0 to 1.
+#[derive(Debug, Clone, Copy, PartialEq, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct AudioSettings {
+    pub master: f32,
+    pub music: f32,
+    pub sfx: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master: 0.3,
+            music: 1.0,
+            sfx: 1.0,
+        }
+    }
+}
+
+impl AudioSettings {
+    /// Volume of a channel, relative to the master volume.
+    pub fn volume(&self, channel: VolumeChannel) -> f32 {
+        match channel {
+            VolumeChannel::Music => self.music,
+            VolumeChannel::Sfx => self.sfx,
+        }
+    }
+
+    fn load(entries: &Entries) -> Self {
+        let default = Self::default();
+        let volume = |key, default| {
+            storage::get(entries, key).map_or(default, |volume: f32| volume.clamp(0.0, 1.0))
+        };
+        Self {
+            master: volume("master_volume", default.master),
+            music: volume("music_volume", default.music),
+            sfx: volume("sfx_volume", default.sfx),
+        }
+    }
+}
+
+/// Which volume setting applies to a sound, on top of the master volume.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component, Reflect)]
+#[reflect(Component)]
+pub enum VolumeChannel {
+    Music,
+    Sfx,
+}
+
+impl VolumeChannel {
+    /// Playback settings starting a sound at the channel's volume.
+    /// The master volume is applied on top as the [`GlobalVolume`].
+    pub fn playback_settings(
+        &self,
+        audio_settings: &AudioSettings,
+        settings: PlaybackSettings,
+    ) -> PlaybackSettings {
+        settings.with_volume(Volume::new(audio_settings.volume(*self)))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct DisplaySettings {
+    pub fullscreen: bool,
+    pub vsync: bool,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            fullscreen: false,
+            vsync: true,
+        }
+    }
+}
+
+impl DisplaySettings {
+    fn load(entries: &Entries) -> Self {
+        let default = Self::default();
+        Self {
+            fullscreen: storage::get(entries, "fullscreen").unwrap_or(default.fullscreen),
+            vsync: storage::get(entries, "vsync").unwrap_or(default.vsync),
+        }
+    }
+}
+
+fn load_language(entries: &Entries) -> Language {
+    entries
+        .get("language")
+        .and_then(|code| {
+            Language::ALL
+                .into_iter()
+                .find(|language| language.code() == code)
+        })
+        .unwrap_or_default()
+}
+
+fn load_accessibility(entries: &Entries) -> Accessibility {
+    let default = Accessibility::default();
+    Accessibility {
+        highlight_colors: entries
+            .get("highlight_colors")
+            .and_then(|key| {
+                HighlightColors::ALL
+                    .into_iter()
+                    .find(|colors| colors.name_key() == key)
+            })
+            .unwrap_or(default.highlight_colors),
+        tile_outlines: storage::get(entries, "tile_outlines").unwrap_or(default.tile_outlines),
+        // Only scales that can be picked, so an edited file can not make the UI unusable
+        text_scale: storage::get(entries, "text_scale")
+            .filter(|scale| TEXT_SCALES.contains(scale))
+            .unwrap_or(default.text_scale),
+    }
+}
+
+/// Only sizes that can be picked, so an edited file can not start a huge map.
+fn load_map_size(entries: &Entries) -> MapSize {
+    storage::get(entries, "map_size")
+        .filter(|size| MapSize::ALL.contains(size))
+        .map_or_else(MapSize::default, MapSize)
+}
+
+fn save_settings(
+    audio_settings: Res<AudioSettings>,
+    display_settings: Res<DisplaySettings>,
+    language: Res<Language>,
+    accessibility: Res<Accessibility>,
+    map_size: Res<MapSize>,
+) {
+    let entries: Entries = [
+        ("master_volume", audio_settings.master.to_string()),
+        ("music_volume", audio_settings.music.to_string()),
+        ("sfx_volume", audio_settings.sfx.to_string()),
+        ("fullscreen", display_settings.fullscreen.to_string()),
+        ("vsync", display_settings.vsync.to_string()),
+        ("language", language.code().to_string()),
+        (
+            "highlight_colors",
+            accessibility.highlight_colors.name_key().to_string(),
+        ),
+        ("tile_outlines", accessibility.tile_outlines.to_string()),
+        ("text_scale", accessibility.text_scale.to_string()),
+        ("map_size", map_size.0.to_string()),
+    ]
+    .into_iter()
+    .map(|(key, value)| (key.to_string(), value))
+    .collect();
+
+    if let Err(error) = storage::save(SETTINGS_FILE, &entries) {
+        error!("Could not save the settings: {error}");
+    }
+}
+
+/// The global volume only applies to sounds started afterwards, so playing sounds are updated too.
+fn apply_volume(
+    audio_settings: Res<AudioSettings>,
+    mut global_volume: ResMut<GlobalVolume>,
+    sink_q: Query<(&AudioSink, &VolumeChannel)>,
+    spatial_sink_q: Query<(&SpatialAudioSink, &VolumeChannel)>,
+) {
+    global_volume.volume = Volume::new(audio_settings.master);
+    for (sink, channel) in &sink_q {
+        sink.set_volume(audio_settings.master * audio_settings.volume(*channel));
+    }
+    for (sink, channel) in &spatial_sink_q {
+        sink.set_volume(audio_settings.master * audio_settings.volume(*channel));
+    }
+}
+
+fn apply_display_settings(
+    display_settings: Res<DisplaySettings>,
+    mut window_q: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if let Ok(mut window) = window_q.get_single_mut() {
+        window.mode = if display_settings.fullscreen {
+            WindowMode::BorderlessFullscreen
+        } else {
+            WindowMode::Windowed
+        };
+        window.present_mode = if display_settings.vsync {
+            PresentMode::AutoVsync
+        } else {
+            PresentMode::AutoNoVsync
+        };
+    }
+}
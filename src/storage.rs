@@ -0,0 +1,65 @@
+//! Keeps small files, like the settings, between runs.
+//! Files hold `key = value` lines. Native builds save them to the platform's config directory,
+//! web builds do not save anything.
+
+use std::{collections::BTreeMap, io, path::PathBuf};
+
+const APP_DIRECTORY: &str = "bevy_jam_tree";
+
+/// Entries of a saved file, sorted by key.
+pub type Entries = BTreeMap<String, String>;
+
+#[cfg(not(target_family = "wasm"))]
+fn directory() -> Option<PathBuf> {
+    use std::env::var_os;
+
+    let base = if cfg!(target_os = "windows") {
+        var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+    base.map(|base| base.join(APP_DIRECTORY))
+}
+
+#[cfg(target_family = "wasm")]
+fn directory() -> Option<PathBuf> {
+    None
+}
+
+/// Entries of a saved file. Empty if it was never saved.
+pub fn load(file_name: &str) -> Entries {
+    directory()
+        .and_then(|directory| std::fs::read_to_string(directory.join(file_name)).ok())
+        .map(|contents| parse(&contents))
+        .unwrap_or_default()
+}
+
+pub fn save(file_name: &str, entries: &Entries) -> io::Result<()> {
+    let Some(directory) = directory() else {
+        return Ok(());
+    };
+    std::fs::create_dir_all(&directory)?;
+
+    let contents: String = entries
+        .iter()
+        .map(|(key, value)| format!("{key} = {value}\n"))
+        .collect();
+    std::fs::write(directory.join(file_name), contents)
+}
+
+fn parse(contents: &str) -> Entries {
+    contents
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().into(), value.trim().into()))
+        .collect()
+}
+
+/// Parses the value of `key`, if there is a valid one.
+pub fn get<T: std::str::FromStr>(entries: &Entries, key: &str) -> Option<T> {
+    entries.get(key).and_then(|value| value.parse().ok())
+}
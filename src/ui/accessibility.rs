@@ -0,0 +1,132 @@
+//! Accessibility settings: highlight colors, tile outlines and text size.
+
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(Accessibility, ScaledText)>();
+    app.init_resource::<Accessibility>();
+
+    app.add_systems(Update, apply_text_scale);
+}
+
+#[derive(Debug, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct Accessibility {
+    pub highlight_colors: HighlightColors,
+    /// Outline the highlighted and selected tiles, instead of only tinting them.
+    pub tile_outlines: bool,
+    pub text_scale: f32,
+}
+
+impl Default for Accessibility {
+    fn default() -> Self {
+        Self {
+            highlight_colors: HighlightColors::Standard,
+            tile_outlines: false,
+            text_scale: 1.0,
+        }
+    }
+}
+
+/// Text scales to cycle through in the settings.
+pub const TEXT_SCALES: [f32; 4] = [0.75, 1.0, 1.25, 1.5];
+
+impl Accessibility {
+    pub fn next_text_scale(&self) -> f32 {
+        TEXT_SCALES
+            .into_iter()
+            .find(|scale| *scale > self.text_scale)
+            .unwrap_or(TEXT_SCALES[0])
+    }
+}
+
+/// Colors marking the highlighted, selected and marked tiles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum HighlightColors {
+    /// Red and green.
+    Standard,
+    /// Orange and blue, for protanopia and deuteranopia.
+    RedGreenSafe,
+    /// Vermilion and bluish green, for tritanopia.
+    BlueYellowSafe,
+}
+
+impl HighlightColors {
+    pub const ALL: [HighlightColors; 3] = [
+        HighlightColors::Standard,
+        HighlightColors::RedGreenSafe,
+        HighlightColors::BlueYellowSafe,
+    ];
+
+    /// Translation key of the name.
+    pub fn name_key(&self) -> &'static str {
+        match self {
+            HighlightColors::Standard => "colors-standard",
+            HighlightColors::RedGreenSafe => "colors-red-green-safe",
+            HighlightColors::BlueYellowSafe => "colors-blue-yellow-safe",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let index = Self::ALL
+            .iter()
+            .position(|colors| colors == self)
+            .unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Color of the tile under the cursor.
+    pub fn highlighted(&self) -> Color {
+        match self {
+            HighlightColors::Standard => Color::srgb(1.0, 0.0, 0.0),
+            HighlightColors::RedGreenSafe => Color::srgb(0.902, 0.624, 0.0),
+            HighlightColors::BlueYellowSafe => Color::srgb(0.835, 0.369, 0.0),
+        }
+    }
+
+    /// Color of the selected tile.
+    pub fn selected(&self) -> Color {
+        match self {
+            HighlightColors::Standard => Color::srgb(0.0, 0.502, 0.0),
+            HighlightColors::RedGreenSafe => Color::srgb(0.0, 0.447, 0.698),
+            HighlightColors::BlueYellowSafe => Color::srgb(0.0, 0.620, 0.451),
+        }
+    }
+
+    /// Color of tiles pointed out to the player, like the tutorial's next move.
+    pub fn marked(&self) -> Color {
+        match self {
+            HighlightColors::Standard => Color::srgb(1.0, 0.843, 0.0),
+            HighlightColors::RedGreenSafe | HighlightColors::BlueYellowSafe => {
+                Color::srgb(0.8, 0.475, 0.655)
+            }
+        }
+    }
+}
+
+/// Text whose sections are sized by [`Accessibility::text_scale`].
+#[derive(Debug, Component, Reflect)]
+#[reflect(Component)]
+pub struct ScaledText {
+    pub font_size: f32,
+}
+
+impl ScaledText {
+    pub fn new(font_size: f32) -> Self {
+        Self { font_size }
+    }
+}
+
+fn apply_text_scale(
+    accessibility: Res<Accessibility>,
+    mut text_q: Query<(Ref<ScaledText>, &mut Text)>,
+) {
+    for (scaled_text, mut text) in &mut text_q {
+        if accessibility.is_changed() || scaled_text.is_added() {
+            let font_size = scaled_text.font_size * accessibility.text_scale;
+            for section in &mut text.sections {
+                section.style.font_size = font_size;
+            }
+        }
+    }
+}
@@ -3,14 +3,18 @@
 // Unused utilities and re-exports may trigger these lints undesirably.
 #![allow(dead_code, unused_imports)]
 
+pub mod accessibility;
 pub mod interaction;
 pub mod palette;
+pub mod slider;
 mod widgets;
 
 pub mod prelude {
     pub use super::{
+        accessibility::{Accessibility, ScaledText},
         interaction::{InteractionPalette, InteractionQuery},
         palette as ui_palette,
+        slider::Slider,
         widgets::{Containers as _, Widgets as _},
     };
 }
@@ -18,5 +22,5 @@ pub mod prelude {
 use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins(interaction::plugin);
+    app.add_plugins((accessibility::plugin, interaction::plugin, slider::plugin));
 }
@@ -0,0 +1,42 @@
+//! Sliders for values from 0 to 1, set by clicking or dragging along them.
+
+use bevy::{prelude::*, ui::RelativeCursorPosition};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<(Slider, SliderFill)>();
+    app.add_systems(Update, (drag_sliders, update_slider_fills).chain());
+}
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct Slider {
+    pub value: f32,
+}
+
+/// The part of a slider filled up to its value.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct SliderFill;
+
+fn drag_sliders(mut slider_q: Query<(&Interaction, &RelativeCursorPosition, &mut Slider)>) {
+    for (interaction, cursor_position, mut slider) in &mut slider_q {
+        if let (Interaction::Pressed, Some(position)) = (interaction, cursor_position.normalized) {
+            let value = position.x.clamp(0.0, 1.0);
+            if slider.value != value {
+                slider.value = value;
+            }
+        }
+    }
+}
+
+fn update_slider_fills(
+    slider_q: Query<(&Slider, &Children), Changed<Slider>>,
+    mut fill_q: Query<&mut Style, With<SliderFill>>,
+) {
+    for (slider, children) in &slider_q {
+        let mut fills = fill_q.iter_many_mut(children);
+        while let Some(mut style) = fills.fetch_next() {
+            style.width = Val::Percent(slider.value * 100.0);
+        }
+    }
+}
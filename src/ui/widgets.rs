@@ -1,29 +1,49 @@
 //! Helper traits for creating common widgets.
 
-use bevy::{ecs::system::EntityCommands, prelude::*, ui::Val::*};
+use bevy::{
+    ecs::system::EntityCommands,
+    prelude::*,
+    ui::{RelativeCursorPosition, Val::*},
+};
 
-use super::{interaction::InteractionPalette, palette::*};
+use super::{
+    accessibility::ScaledText,
+    interaction::InteractionPalette,
+    palette::*,
+    slider::{Slider, SliderFill},
+};
+use crate::locale::LocalizedText;
 
 /// An extension trait for spawning UI widgets.
+/// Their `text` is a translation key, except for [`Widgets::label_raw`].
+/// Keys without a translation are shown as is and logged.
 pub trait Widgets {
     /// Spawn a simple button with text.
-    fn button(&mut self, text: impl Into<String>) -> EntityCommands;
+    fn button(&mut self, text: impl Into<String>) -> EntityCommands<'_>;
 
     /// Spawn a simple header label. Bigger than [`Widgets::label`].
-    fn header(&mut self, text: impl Into<String>) -> EntityCommands;
+    fn header(&mut self, text: impl Into<String>) -> EntityCommands<'_>;
 
     /// Spawn a simple text label.
-    fn label(&mut self, text: impl Into<String>) -> EntityCommands;
+    fn label(&mut self, text: impl Into<String>) -> EntityCommands<'_>;
+
+    /// Spawn a simple text label showing `text` as is, like a name or a number.
+    fn label_raw(&mut self, text: impl Into<String>) -> EntityCommands<'_>;
+
+    /// Spawn a slider with text on it, starting at `value` between 0 and 1.
+    fn slider(&mut self, text: impl Into<String>, value: f32) -> EntityCommands<'_>;
 }
 
 impl<T: Spawn> Widgets for T {
-    fn button(&mut self, text: impl Into<String>) -> EntityCommands {
+    fn button(&mut self, text: impl Into<String>) -> EntityCommands<'_> {
         let mut entity = self.spawn((
             Name::new("Button"),
             ButtonBundle {
                 style: Style {
-                    width: Px(200.0),
-                    height: Px(65.0),
+                    // Grow with larger text
+                    min_width: Px(200.0),
+                    min_height: Px(65.0),
+                    padding: UiRect::horizontal(Px(10.0)),
                     justify_content: JustifyContent::Center,
                     align_items: AlignItems::Center,
                     ..default()
@@ -41,25 +61,27 @@ impl<T: Spawn> Widgets for T {
             children.spawn((
                 Name::new("Button Text"),
                 TextBundle::from_section(
-                    text,
+                    "",
                     TextStyle {
                         font_size: 40.0,
                         color: BUTTON_TEXT,
                         ..default()
                     },
                 ),
+                ScaledText::new(40.0),
+                LocalizedText::new(text),
             ));
         });
         entity
     }
 
-    fn header(&mut self, text: impl Into<String>) -> EntityCommands {
+    fn header(&mut self, text: impl Into<String>) -> EntityCommands<'_> {
         let mut entity = self.spawn((
             Name::new("Header"),
             NodeBundle {
                 style: Style {
                     width: Px(500.0),
-                    height: Px(65.0),
+                    min_height: Px(65.0),
                     justify_content: JustifyContent::Center,
                     align_items: AlignItems::Center,
                     ..default()
@@ -72,57 +94,126 @@ impl<T: Spawn> Widgets for T {
             children.spawn((
                 Name::new("Header Text"),
                 TextBundle::from_section(
-                    text,
+                    "",
                     TextStyle {
                         font_size: 40.0,
                         color: HEADER_TEXT,
                         ..default()
                     },
                 ),
+                ScaledText::new(40.0),
+                LocalizedText::new(text),
             ));
         });
         entity
     }
 
-    fn label(&mut self, text: impl Into<String>) -> EntityCommands {
+    fn label(&mut self, text: impl Into<String>) -> EntityCommands<'_> {
+        let mut entity = self.spawn(label_node());
+        entity.with_children(|children| {
+            children.spawn((label_text(""), LocalizedText::new(text)));
+        });
+        entity
+    }
+
+    fn label_raw(&mut self, text: impl Into<String>) -> EntityCommands<'_> {
+        let mut entity = self.spawn(label_node());
+        entity.with_children(|children| {
+            children.spawn(label_text(text));
+        });
+        entity
+    }
+
+    fn slider(&mut self, text: impl Into<String>, value: f32) -> EntityCommands<'_> {
         let mut entity = self.spawn((
-            Name::new("Label"),
-            NodeBundle {
+            Name::new("Slider"),
+            ButtonBundle {
                 style: Style {
                     width: Px(500.0),
+                    min_height: Px(65.0),
                     justify_content: JustifyContent::Center,
                     align_items: AlignItems::Center,
                     ..default()
                 },
+                background_color: BackgroundColor(NODE_BACKGROUND),
                 ..default()
             },
+            RelativeCursorPosition::default(),
+            Slider { value },
         ));
         entity.with_children(|children| {
             children.spawn((
-                Name::new("Label Text"),
+                Name::new("Slider Fill"),
+                NodeBundle {
+                    style: Style {
+                        width: Percent(value * 100.0),
+                        height: Percent(100.0),
+                        position_type: PositionType::Absolute,
+                        left: Px(0.0),
+                        ..default()
+                    },
+                    background_color: BackgroundColor(SLIDER_FILL),
+                    ..default()
+                },
+                SliderFill,
+            ));
+            children.spawn((
+                Name::new("Slider Text"),
                 TextBundle::from_section(
-                    text,
+                    "",
                     TextStyle {
-                        font_size: 24.0,
-                        color: LABEL_TEXT,
+                        font_size: 40.0,
+                        color: BUTTON_TEXT,
                         ..default()
                     },
                 ),
+                ScaledText::new(40.0),
+                LocalizedText::new(text),
             ));
         });
         entity
     }
 }
 
+fn label_node() -> impl Bundle {
+    (
+        Name::new("Label"),
+        NodeBundle {
+            style: Style {
+                width: Px(500.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        },
+    )
+}
+
+fn label_text(text: impl Into<String>) -> impl Bundle {
+    (
+        Name::new("Label Text"),
+        TextBundle::from_section(
+            text,
+            TextStyle {
+                font_size: 24.0,
+                color: LABEL_TEXT,
+                ..default()
+            },
+        ),
+        ScaledText::new(24.0),
+    )
+}
+
 /// An extension trait for spawning UI containers.
 pub trait Containers {
     /// Spawns a root node that covers the full screen
     /// and centers its content horizontally and vertically.
-    fn ui_root(&mut self) -> EntityCommands;
+    fn ui_root(&mut self) -> EntityCommands<'_>;
 }
 
 impl Containers for Commands<'_, '_> {
-    fn ui_root(&mut self) -> EntityCommands {
+    fn ui_root(&mut self) -> EntityCommands<'_> {
         self.spawn((
             Name::new("UI Root"),
             NodeBundle {
@@ -147,17 +238,17 @@ impl Containers for Commands<'_, '_> {
 /// are able to spawn entities.
 /// Ideally, this trait should be [part of Bevy itself](https://github.com/bevyengine/bevy/issues/14231).
 trait Spawn {
-    fn spawn<B: Bundle>(&mut self, bundle: B) -> EntityCommands;
+    fn spawn<B: Bundle>(&mut self, bundle: B) -> EntityCommands<'_>;
 }
 
 impl Spawn for Commands<'_, '_> {
-    fn spawn<B: Bundle>(&mut self, bundle: B) -> EntityCommands {
+    fn spawn<B: Bundle>(&mut self, bundle: B) -> EntityCommands<'_> {
         self.spawn(bundle)
     }
 }
 
 impl Spawn for ChildBuilder<'_> {
-    fn spawn<B: Bundle>(&mut self, bundle: B) -> EntityCommands {
+    fn spawn<B: Bundle>(&mut self, bundle: B) -> EntityCommands<'_> {
         self.spawn(bundle)
     }
 }